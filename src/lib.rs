@@ -0,0 +1,4 @@
+pub mod rust_game_engine;
+// the demo needs a window
+#[cfg(feature = "raylib")]
+pub mod project;
@@ -1,7 +1,4 @@
-mod rust_game_engine;
-mod project;
-
-use rust_game_engine::engine_init::init_game;
+use raylib_test::rust_game_engine::engine_init::init_game;
 
 fn main() {
     init_game();
@@ -1,50 +1,81 @@
 use crate::rust_game_engine::constants::*;
 use crate::rust_game_engine::engine_core::*;
+use crate::rust_game_engine::math::Vector2;
+use crate::rust_game_engine::physics::contact_events::ContactEventKind;
 use crate::rust_game_engine::physics::game_object::PhysicsObject;
-use crate::rust_game_engine::physics::physics_addition::PhysicsAddition;
+use crate::rust_game_engine::physics::material::Material;
 use crate::rust_game_engine::timer::Timer;
 use rand::Rng;
-use raylib::prelude::Vector2;
 
 /// This function is called once when the scene is constructed
 pub fn setup(default_scene: &mut Scene) {
     println!("Hello world! from setup");
 
-    default_scene.game_objects.push(PhysicsObject::new(
+    default_scene.add_game_object(PhysicsObject::new(
         Vector2::new(100., 100.),
-        2000.,
+        25.,
+        Material::RUBBER,
         "player".to_string(),
+    )
+    .into_kinematic());
+    // kinematic objects don't collide with static ones, the ball shows the contact events
+    default_scene.add_game_object(PhysicsObject::new_circle(
+        Vector2::new(500., 100.),
+        20.,
+        Material::RUBBER,
+        "ball".to_string(),
     ));
 
-    default_scene
-        .game_objects
-        .extend(PhysicsObject::generate_walls());
+    for wall in PhysicsObject::generate_walls() {
+        default_scene.add_game_object(wall);
+    }
 
-    default_scene
-        .game_objects
-        .push(PhysicsObject::generate_ground(Vector2::new(500., 500.)));
+    default_scene.add_game_object(PhysicsObject::generate_ground(Vector2::new(500., 500.)));
 }
 
 /// This function is called every frame and provides the delta time in s
-pub fn frame(scene: &mut Scene, delta_time: f32) {
+pub fn frame(scene: &mut Scene, _delta_time: f32) {
     let mut rng = rand::rng();
 
-    if scene.mouse_clicked() {
-        let mut obj: PhysicsObject = PhysicsObject::new(
+    // grab objects under the mouse, spawn new ones everywhere else
+    let is_dragging: bool = scene.drag_with_mouse();
+    if scene.mouse_clicked() && !is_dragging {
+        let obj: PhysicsObject = PhysicsObject::new(
             scene.mouse_pos(),
-            rng.random::<f32>() * 3000.,
+            rng.random_range(5. ..30.),
+            Material::default(),
             "_".to_string(),
         );
-
-        if let PhysicsAddition::Dynamic { ref mut vel, .. } = obj.physics {
-            *vel = Vector2::new(0., 0.);
-        }
         scene.add_game_object(obj);
     }
 
     let key_dir: Vector2 = scene.get_key_direction();
-    let main_obj: &mut PhysicsObject = scene.game_objects.first_mut().unwrap();
-    main_obj.move_relative(&(key_dir * 100. * delta_time));
+    if let Some(main_obj) = scene.get_by_name_mut("player") {
+        // walls don't stop kinematic objects, so the player stops at the edge of the screen
+        let pos: Vector2 = main_obj.obj.pos;
+        let mut vel: Vector2 = key_dir * 100.;
+        let margin: f32 = 25.;
+        if pos.x < margin && vel.x < 0. || pos.x > WIDTH_F - margin && vel.x > 0. {
+            vel.x = 0.;
+        }
+        if pos.y < margin && vel.y < 0. || pos.y > HEIGHT_F - margin && vel.y > 0. {
+            vel.y = 0.;
+        }
+        if let Some(player_vel) = main_obj.physics.get_vel_mut() {
+            *player_vel = vel;
+        }
+    }
+
+    if let (Some(ball), Some(ground)) = (
+        scene.get_id_by_name("ball"),
+        scene.get_id_by_name("ground_obj"),
+    ) {
+        for event in &scene.contact_events {
+            if event.kind == ContactEventKind::Begin && event.get_other(ball) == Some(ground) {
+                println!("ball touched the ground with impulse {}", event.impulse);
+            }
+        }
+    }
 
     if scene.timers.is_empty() {
         spawn_one_timer(scene);
@@ -55,19 +86,16 @@ pub fn frame(scene: &mut Scene, delta_time: f32) {
 fn spawn_random(scene: &mut Scene) {
     let mut rng = rand::rng();
     let (pos_x, pos_y) = rng.random::<(f32, f32)>();
-    let mass: f32 = rng.random_range(1000. ..10000.);
+    let radius: f32 = rng.random_range(18. ..56.);
     let vel: Vector2 = Vector2::new(0., 0.);
 
-    let mut obj: PhysicsObject = PhysicsObject::new(
-        Vector2::new(pos_x * WIDTH_F, pos_y * HEIGHT_F),
-        mass,
-        "_".to_string(),
-    );
-    if let PhysicsAddition::Dynamic {
-        vel: ref mut new_vel,
-        ..
-    } = obj.physics
-    {
+    let pos: Vector2 = Vector2::new(pos_x * WIDTH_F, pos_y * HEIGHT_F);
+    let mut obj: PhysicsObject = match rng.random_range(0..3) {
+        0 => PhysicsObject::new_circle(pos, radius, Material::WOOD, "_".to_string()),
+        1 => PhysicsObject::new_capsule(pos, radius, radius / 2., Material::WOOD, "_".to_string()),
+        _ => PhysicsObject::new(pos, radius, Material::WOOD, "_".to_string()),
+    };
+    if let Some(new_vel) = obj.physics.get_vel_mut() {
         *new_vel = vel;
     }
 
@@ -0,0 +1,66 @@
+use crate::rust_game_engine::backend::{Backend, DrawCommand};
+use crate::rust_game_engine::constants::*;
+use crate::rust_game_engine::input::{KeyboardKey, MouseButton};
+use crate::rust_game_engine::math::Vector2;
+
+/// No window: the clock advances by a fixed frame time every frame
+/// and the input is whatever the user code sets, e.g. for tests on a machine without display
+pub struct HeadlessBackend {
+    pub frame_time: f32,
+    pub run_time: f64,
+    pub close_requested: bool,
+
+    // simulated input
+    pub mouse_pos: Vector2,
+    pub mouse_buttons_pressed: Vec<MouseButton>,
+    pub mouse_buttons_down: Vec<MouseButton>,
+    pub keys_down: Vec<KeyboardKey>,
+}
+
+impl HeadlessBackend {
+    pub fn new(frame_time: f32) -> HeadlessBackend {
+        HeadlessBackend {
+            frame_time,
+            run_time: 0.,
+            close_requested: false,
+            mouse_pos: Vector2::zero(),
+            mouse_buttons_pressed: vec![],
+            mouse_buttons_down: vec![],
+            keys_down: vec![],
+        }
+    }
+}
+
+impl Backend for HeadlessBackend {
+    fn should_close(&self) -> bool {
+        self.close_requested
+    }
+    fn begin_frame(&mut self) {
+        self.run_time += self.frame_time as f64;
+    }
+
+    fn get_frame_time(&self) -> f32 {
+        self.frame_time
+    }
+    fn get_run_time(&self) -> f32 {
+        self.run_time as f32
+    }
+    fn get_screen_size(&self) -> (i32, i32) {
+        (WIDTH, HEIGHT)
+    }
+
+    fn mouse_pos(&self) -> Vector2 {
+        self.mouse_pos
+    }
+    fn is_mouse_button_pressed(&self, button: MouseButton) -> bool {
+        self.mouse_buttons_pressed.contains(&button)
+    }
+    fn is_mouse_button_down(&self, button: MouseButton) -> bool {
+        self.mouse_buttons_down.contains(&button)
+    }
+    fn is_key_down(&self, key: KeyboardKey) -> bool {
+        self.keys_down.contains(&key)
+    }
+
+    fn draw(&mut self, _commands: &[DrawCommand]) {}
+}
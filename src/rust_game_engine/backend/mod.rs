@@ -0,0 +1,50 @@
+pub mod headless_backend;
+#[cfg(feature = "raylib")]
+pub mod raylib_backend;
+
+use crate::rust_game_engine::color::Color;
+use crate::rust_game_engine::input::{KeyboardKey, MouseButton};
+use crate::rust_game_engine::math::Vector2;
+
+/// Everything the scene needs from a window: time, input and drawing.
+/// The simulation itself never talks to raylib directly, only `RaylibBackend` needs the `raylib` feature
+pub trait Backend {
+    fn should_close(&self) -> bool;
+    /// called once at the start of every frame
+    fn begin_frame(&mut self) {}
+
+    /// length of the last frame in s
+    fn get_frame_time(&self) -> f32;
+    /// time since start in s
+    fn get_run_time(&self) -> f32;
+    fn get_screen_size(&self) -> (i32, i32);
+
+    fn mouse_pos(&self) -> Vector2;
+    fn is_mouse_button_pressed(&self, button: MouseButton) -> bool;
+    fn is_mouse_button_down(&self, button: MouseButton) -> bool;
+    fn is_key_down(&self, key: KeyboardKey) -> bool;
+
+    fn draw(&mut self, commands: &[DrawCommand]);
+}
+
+/// Backend independent description of what to draw
+pub enum DrawCommand {
+    Clear(Color),
+    Line {
+        start: Vector2,
+        end: Vector2,
+        thickness: f32,
+        color: Color,
+    },
+    Text {
+        text: String,
+        x: i32,
+        y: i32,
+        size: i32,
+        color: Color,
+    },
+    Fps {
+        x: i32,
+        y: i32,
+    },
+}
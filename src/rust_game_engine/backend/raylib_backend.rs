@@ -0,0 +1,108 @@
+use crate::rust_game_engine::backend::{Backend, DrawCommand};
+use crate::rust_game_engine::color::Color;
+use crate::rust_game_engine::input::{KeyboardKey, MouseButton};
+use crate::rust_game_engine::math::Vector2;
+use raylib::prelude as rl;
+use raylib::prelude::{RaylibDraw, RaylibHandle, RaylibThread};
+
+/// Window, input and drawing through raylib
+pub struct RaylibBackend {
+    pub rl: RaylibHandle,
+    pub rl_thread: RaylibThread,
+}
+
+impl RaylibBackend {
+    pub fn new(rl: RaylibHandle, rl_thread: RaylibThread) -> RaylibBackend {
+        RaylibBackend { rl, rl_thread }
+    }
+}
+
+impl Backend for RaylibBackend {
+    fn should_close(&self) -> bool {
+        self.rl.window_should_close()
+    }
+
+    fn get_frame_time(&self) -> f32 {
+        self.rl.get_frame_time()
+    }
+    fn get_run_time(&self) -> f32 {
+        self.rl.get_time() as f32
+    }
+    fn get_screen_size(&self) -> (i32, i32) {
+        (self.rl.get_screen_width(), self.rl.get_screen_height())
+    }
+
+    fn mouse_pos(&self) -> Vector2 {
+        let pos: rl::Vector2 = self.rl.get_mouse_position();
+        Vector2::new(pos.x, pos.y)
+    }
+    fn is_mouse_button_pressed(&self, button: MouseButton) -> bool {
+        self.rl.is_mouse_button_pressed(to_raylib_button(button))
+    }
+    fn is_mouse_button_down(&self, button: MouseButton) -> bool {
+        self.rl.is_mouse_button_down(to_raylib_button(button))
+    }
+    fn is_key_down(&self, key: KeyboardKey) -> bool {
+        self.rl.is_key_down(to_raylib_key(key))
+    }
+
+    fn draw(&mut self, commands: &[DrawCommand]) {
+        let mut d = self.rl.begin_drawing(&self.rl_thread);
+        for command in commands {
+            match command {
+                DrawCommand::Clear(color) => d.clear_background(to_raylib_color(*color)),
+                DrawCommand::Line {
+                    start,
+                    end,
+                    thickness,
+                    color,
+                } => d.draw_line_ex(
+                    to_raylib_vector(*start),
+                    to_raylib_vector(*end),
+                    *thickness,
+                    to_raylib_color(*color),
+                ),
+                DrawCommand::Text {
+                    text,
+                    x,
+                    y,
+                    size,
+                    color,
+                } => d.draw_text(text, *x, *y, *size, to_raylib_color(*color)),
+                DrawCommand::Fps { x, y } => d.draw_fps(*x, *y),
+            }
+        }
+    }
+}
+
+fn to_raylib_vector(v: Vector2) -> rl::Vector2 {
+    rl::Vector2::new(v.x, v.y)
+}
+
+fn to_raylib_color(color: Color) -> rl::Color {
+    rl::Color::new(color.r, color.g, color.b, color.a)
+}
+
+fn to_raylib_button(button: MouseButton) -> rl::MouseButton {
+    match button {
+        MouseButton::Left => rl::MouseButton::MOUSE_BUTTON_LEFT,
+        MouseButton::Right => rl::MouseButton::MOUSE_BUTTON_RIGHT,
+        MouseButton::Middle => rl::MouseButton::MOUSE_BUTTON_MIDDLE,
+    }
+}
+
+fn to_raylib_key(key: KeyboardKey) -> rl::KeyboardKey {
+    match key {
+        KeyboardKey::Left => rl::KeyboardKey::KEY_LEFT,
+        KeyboardKey::Right => rl::KeyboardKey::KEY_RIGHT,
+        KeyboardKey::Up => rl::KeyboardKey::KEY_UP,
+        KeyboardKey::Down => rl::KeyboardKey::KEY_DOWN,
+        KeyboardKey::W => rl::KeyboardKey::KEY_W,
+        KeyboardKey::A => rl::KeyboardKey::KEY_A,
+        KeyboardKey::S => rl::KeyboardKey::KEY_S,
+        KeyboardKey::D => rl::KeyboardKey::KEY_D,
+        KeyboardKey::Space => rl::KeyboardKey::KEY_SPACE,
+        KeyboardKey::Enter => rl::KeyboardKey::KEY_ENTER,
+        KeyboardKey::Escape => rl::KeyboardKey::KEY_ESCAPE,
+    }
+}
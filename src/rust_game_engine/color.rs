@@ -0,0 +1,24 @@
+/// RGBA color, 255 alpha is opaque. The constants have the same values as the raylib ones
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Color {
+    pub const BLACK: Color = Color::new(0, 0, 0, 255);
+    pub const WHITE: Color = Color::new(255, 255, 255, 255);
+    pub const WHITESMOKE: Color = Color::new(245, 245, 245, 255);
+    pub const DARKGRAY: Color = Color::new(169, 169, 169, 255);
+    pub const RED: Color = Color::new(255, 0, 0, 255);
+    pub const ORANGE: Color = Color::new(255, 165, 0, 255);
+    pub const GREEN: Color = Color::new(0, 128, 0, 255);
+    pub const BLUE: Color = Color::new(0, 0, 255, 255);
+    pub const DARKBLUE: Color = Color::new(0, 0, 139, 255);
+
+    pub const fn new(r: u8, g: u8, b: u8, a: u8) -> Color {
+        Color { r, g, b, a }
+    }
+}
@@ -2,6 +2,38 @@ pub const WIDTH: i32 = 1080;
 pub const HEIGHT: i32 = 720;
 pub const WIDTH_F: f32 = 1080.;
 pub const HEIGHT_F: f32 = 720.;
-pub const BOUNCINESS: f32 = 0.9;
 
-pub const GRAVITY: f32 = 300.;
\ No newline at end of file
+pub const GRAVITY: f32 = 300.;
+
+// fixed time step
+pub const PHYSICS_HZ: f32 = 60.;
+pub const MAX_STEPS_PER_FRAME: usize = 5;
+
+// contact solver
+pub const SUB_STEPS: usize = 1;
+pub const VELOCITY_ITERATIONS: usize = 8;
+pub const POSITION_ITERATIONS: usize = 3;
+/// objects hitting each other slower than this (px/s) do not bounce
+pub const RESTITUTION_VELOCITY_THRESHOLD: f32 = 20.;
+/// overlap (px) that is allowed to stay, prevents jitter of resting objects
+pub const PENETRATION_SLOP: f32 = 0.5;
+pub const POSITION_CORRECTION_PERCENT: f32 = 0.8;
+
+// sleeping
+/// objects slower than this (px/s) for `TIME_TO_SLEEP` s fall asleep
+pub const SLEEP_LINEAR_THRESHOLD: f32 = 8.;
+/// rad/s
+pub const SLEEP_ANGULAR_THRESHOLD: f32 = 0.2;
+pub const TIME_TO_SLEEP: f32 = 0.5;
+
+// joints
+/// fraction of the error of rigid joints that is corrected each step
+pub const JOINT_BAUMGARTE: f32 = 0.2;
+
+// dragging with the mouse
+/// Hz, how fast dragged objects follow the mouse
+pub const DRAG_FREQUENCY: f32 = 5.;
+/// 1 is critically damped
+pub const DRAG_DAMPING_RATIO: f32 = 0.7;
+/// px/s², limits the force so that dragged objects can't be pushed through walls
+pub const DRAG_MAX_ACCELERATION: f32 = 5000.;
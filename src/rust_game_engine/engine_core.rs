@@ -1,32 +1,91 @@
 use std::collections::HashSet;
 use std::ops::Range;
-use raylib::ffi::KeyboardKey::KEY_LEFT;
+use rand::Rng;
+use crate::rust_game_engine::backend::headless_backend::HeadlessBackend;
+use crate::rust_game_engine::backend::{Backend, DrawCommand};
+use crate::rust_game_engine::color::Color;
+use crate::rust_game_engine::constants::*;
+use crate::rust_game_engine::fixed_time_step::FixedTimeStep;
+use crate::rust_game_engine::input::{KeyboardKey, MouseButton};
+use crate::rust_game_engine::math::Vector2;
+use crate::rust_game_engine::object_store::{ObjectId, ObjectStore};
+use crate::rust_game_engine::physics::ccd::Impact;
+use crate::rust_game_engine::physics::contact::Contact;
+use crate::rust_game_engine::physics::contact_events::{
+    ContactCallback, ContactEvent, ContactTracker,
+};
 use crate::rust_game_engine::physics::game_object::{PhysicsObject};
+use crate::rust_game_engine::physics::joint::Joint;
+use crate::rust_game_engine::physics::material::MaterialMixing;
+use crate::rust_game_engine::physics::mouse_drag::MouseDrag;
+use crate::rust_game_engine::physics::sensor::{SensorCallback, SensorEvent, SensorTracker};
+use crate::rust_game_engine::physics::sleep;
+use crate::rust_game_engine::physics::sleep_config::SleepConfig;
+use crate::rust_game_engine::physics::solver_config::SolverConfig;
+use crate::rust_game_engine::tag_index::TagIndex;
 use crate::rust_game_engine::timer::Timer;
-use raylib::prelude::MouseButton::MOUSE_BUTTON_LEFT;
-use raylib::prelude::*;
-use raylib::prelude::KeyboardKey::{KEY_DOWN, KEY_RIGHT, KEY_UP};
 
 pub struct Scene {
     pub timers: Vec<Timer>,
-    pub game_objects: Vec<PhysicsObject>,
+    /// only changed through the scene, so that the tag index and the joints stay up to date
+    pub(crate) game_objects: ObjectStore<PhysicsObject>,
+    /// names and tags of `game_objects`, updated by `add_game_object` and `remove_game_object`
+    pub(crate) tag_index: TagIndex,
+    /// constraints between `game_objects`, removed together with their objects
+    pub(crate) joints: ObjectStore<Joint>,
+    /// object grabbed with `drag_with_mouse`
+    pub mouse_drag: MouseDrag,
     pub space_partitioning_grid_size: (usize, usize),
+    pub material_mixing: MaterialMixing,
+    pub time_step: FixedTimeStep,
+    pub solver_config: SolverConfig,
+    pub sleep_config: SleepConfig,
+    /// acceleration of all dynamic objects, scaled by their gravity scale.
+    /// Private, so that `set_gravity` can wake up resting objects
+    gravity: Vector2,
 
-    pub rl: RaylibHandle,
-    pub rl_thread: RaylibThread,
+    /// begin, persist and end events of all physics steps in the current frame
+    pub contact_events: Vec<ContactEvent>,
+    pub contact_tracker: ContactTracker,
+    pub contact_callbacks: Vec<ContactCallback>,
+    /// enter and exit events of all sensors in the current frame
+    pub sensor_events: Vec<SensorEvent>,
+    pub sensor_tracker: SensorTracker,
+    pub sensor_callbacks: Vec<SensorCallback>,
+
+    /// window, input and clock
+    pub backend: Box<dyn Backend>,
 }
 
 impl Scene {
-    pub fn new(rl: RaylibHandle, rl_thread: RaylibThread) -> Self {
+    pub fn new(backend: Box<dyn Backend>) -> Self {
         Self {
             timers: vec![],
-            game_objects: vec![],
+            game_objects: ObjectStore::new(),
+            tag_index: TagIndex::new(),
+            joints: ObjectStore::new(),
+            mouse_drag: MouseDrag::default(),
             space_partitioning_grid_size: (10, 10),
-            rl,
-            rl_thread,
+            material_mixing: MaterialMixing::default(),
+            time_step: FixedTimeStep::default(),
+            solver_config: SolverConfig::default(),
+            sleep_config: SleepConfig::default(),
+            gravity: Vector2::new(0., GRAVITY),
+            contact_events: vec![],
+            contact_tracker: ContactTracker::new(),
+            contact_callbacks: vec![],
+            sensor_events: vec![],
+            sensor_tracker: SensorTracker::new(),
+            sensor_callbacks: vec![],
+            backend,
         }
     }
 
+    /// scene without a window, every frame advances the clock by `frame_time`
+    pub fn new_headless(frame_time: f32) -> Self {
+        Self::new(Box::new(HeadlessBackend::new(frame_time)))
+    }
+
     pub fn frame_logic(&mut self, delta_time: f32) {
         // timers
         let current_time: f32 = self.get_run_time();
@@ -40,114 +99,552 @@ impl Scene {
         }
         self.timers = timers_left;
 
+        // physics, independent of the frame rate
+        self.contact_events.clear();
+        self.sensor_events.clear();
+        let steps: usize = self.time_step.advance(delta_time);
+        let step_time: f32 = self.time_step.get_step_time();
+        for _ in 0..steps {
+            self.physics_step(step_time);
+        }
+        // contact callbacks, after the physics so that they can add and remove objects
+        let mut callbacks: Vec<ContactCallback> = std::mem::take(&mut self.contact_callbacks);
+        let events: Vec<ContactEvent> = self.contact_events.clone();
+        for event in &events {
+            for callback in &mut callbacks {
+                callback(self, event);
+            }
+        }
+        // callbacks registered during the callbacks
+        callbacks.append(&mut self.contact_callbacks);
+        self.contact_callbacks = callbacks;
 
+        let mut callbacks: Vec<SensorCallback> = std::mem::take(&mut self.sensor_callbacks);
+        let events: Vec<SensorEvent> = self.sensor_events.clone();
+        for event in &events {
+            for callback in &mut callbacks {
+                callback(self, event);
+            }
+        }
+        callbacks.append(&mut self.sensor_callbacks);
+        self.sensor_callbacks = callbacks;
+    }
+
+    /// for top down scenes, same as setting the gravity to zero
+    pub fn disable_gravity(&mut self) {
+        self.set_gravity(Vector2::zero());
+    }
+
+    pub fn get_gravity(&self) -> Vector2 {
+        self.gravity
+    }
+
+    /// wakes all dynamic objects up, resting objects have to react to the new direction
+    pub fn set_gravity(&mut self, gravity: Vector2) {
+        self.gravity = gravity;
+        for obj in self.game_objects.values_mut() {
+            obj.physics.wake_up();
+        }
+    }
+
+    /// `callback` is called for every contact event, see `contact_events`
+    pub fn on_contact(&mut self, callback: ContactCallback) {
+        self.contact_callbacks.push(callback);
+    }
+    /// `callback` is called for every sensor event, see `sensor_events`
+    pub fn on_sensor(&mut self, callback: SensorCallback) {
+        self.sensor_callbacks.push(callback);
+    }
+
+    /// advances the simulation by exactly `delta_time`, split into the configured sub steps
+    pub fn physics_step(&mut self, delta_time: f32) {
+        for obj in self.game_objects.values_mut() {
+            obj.save_previous_transform();
+        }
+
+        let sub_steps: usize = self.solver_config.sub_steps.max(1);
+        let sub_step_time: f32 = delta_time / sub_steps as f32;
+        for _ in 0..sub_steps {
+            self.physics_sub_step(sub_step_time);
+        }
+
+        // resting pairs are skipped by the narrow phase, they touch until one of them wakes up
+        let is_resting = |id: ObjectId| {
+            self.game_objects
+                .get(id)
+                .is_some_and(|obj| !sleep::is_moving(obj))
+        };
+        self.contact_tracker.keep_resting_contacts(is_resting);
+        let events: Vec<ContactEvent> = self.contact_tracker.finish_step();
+        self.contact_events.extend(events);
+        let events: Vec<SensorEvent> = self.sensor_tracker.finish_step();
+        self.sensor_events.extend(events);
+
+        // applied forces act on exactly one step, frames without a step keep them for the next one
+        for obj in self.game_objects.values_mut() {
+            obj.physics.clear_forces();
+        }
+
+        self.update_sleep(delta_time);
+    }
+
+    fn physics_sub_step(&mut self, delta_time: f32) {
         // move
-        for obj in &mut self.game_objects {
-            obj.update_move(delta_time);
+        let start_positions: Vec<(ObjectId, Vector2)> = self
+            .game_objects
+            .iter()
+            .filter(|(_, obj)| !obj.physics.is_static())
+            .map(|(id, obj)| (id, obj.obj.pos))
+            .collect();
+        for obj in self.game_objects.values_mut() {
+            obj.update_move(delta_time, self.gravity);
         }
+        let ccd_remaining: Vec<(ObjectId, Impact)> = self.clamp_to_time_of_impact(&start_positions);
 
+        let possible_collisions: HashSet<(ObjectId, ObjectId)> = self.get_possible_collisions();
+        let overlaps: Vec<(ObjectId, ObjectId)> = self.filter_sensor_overlaps(&possible_collisions);
+        self.sensor_tracker.add_overlaps(&overlaps);
 
-        let possible_collisions: HashSet<(usize, usize)> = self.get_possible_collisions();
-        let real_collisions: Vec<(usize, usize)> = self.filter_real_collisions(possible_collisions);
-        self.resolve_collisions(&real_collisions);
+        // every contact has a moving object, sleeping objects touched by it are woken up
+        let mut contacts: Vec<Contact> = self.filter_real_collisions(possible_collisions);
+        self.wake_touched_objects(&contacts);
+        self.wake_joined_objects();
+        self.resolve_collisions(&mut contacts, delta_time);
+        self.contact_tracker.add_contacts(&contacts);
+        self.continue_after_impact(&ccd_remaining, delta_time);
 
         /*
-        for obj in &mut self.game_objects {
+        for obj in self.game_objects.values_mut() {
             obj.obj.color = Color::BLUE;
         }
 
         for &(i, j) in &possible_collisions {
-            self.game_objects[i].obj.color = Color::ORANGE;
-            self.game_objects[j].obj.color = Color::ORANGE;
+            let (a, b) = self.game_objects.get_pair_mut(i, j).unwrap();
+            a.obj.color = Color::ORANGE;
+            b.obj.color = Color::ORANGE;
         }
-        for &(i, j) in &real_collisions {
-            self.game_objects[i].obj.color = Color::RED;
-            self.game_objects[j].obj.color = Color::RED;
+        for &Contact { i, j, .. } in &contacts {
+            let (a, b) = self.game_objects.get_pair_mut(i, j).unwrap();
+            a.obj.color = Color::RED;
+            b.obj.color = Color::RED;
         }
          */
     }
 
-    // todo
-    // pub fn get_object_by_name_tag_mut(&mut self, name_tag: &str) -> Option<&mut PhysicsObject> {}
-    pub fn get_first_object_mut(&mut self) -> &mut PhysicsObject {
-        self.game_objects.first_mut().unwrap()
+    /// the object with the smallest id among all objects with this name tag
+    pub fn get_id_by_name(&self, name_tag: &str) -> Option<ObjectId> {
+        self.tag_index
+            .get_ids_by_name(name_tag)
+            .find(|&id| self.game_objects.contains(id))
+    }
+    pub fn get_by_name(&self, name_tag: &str) -> Option<&PhysicsObject> {
+        self.game_objects.get(self.get_id_by_name(name_tag)?)
+    }
+    pub fn get_by_name_mut(&mut self, name_tag: &str) -> Option<&mut PhysicsObject> {
+        self.game_objects.get_mut(self.get_id_by_name(name_tag)?)
+    }
+
+    pub fn iter_with_tag(&self, tag: &str) -> impl Iterator<Item = (ObjectId, &PhysicsObject)> {
+        self.tag_index
+            .get_ids_with_tag(tag)
+            .filter_map(|id| Some((id, self.game_objects.get(id)?)))
+    }
+    pub fn iter_with_tag_mut(
+        &mut self,
+        tag: &str,
+    ) -> impl Iterator<Item = (ObjectId, &mut PhysicsObject)> {
+        self.game_objects
+            .iter_mut_sorted(self.tag_index.get_ids_with_tag(tag))
+    }
+
+    /// `false` if the object was already removed
+    pub fn set_name_tag(&mut self, id: ObjectId, name_tag: &str) -> bool {
+        let Some(obj) = self.game_objects.get_mut(id) else {
+            return false;
+        };
+        self.tag_index.rename(id, obj.obj.get_name_tag(), name_tag);
+        obj.obj.set_name_tag(name_tag);
+        true
+    }
+    /// `false` if the object was already removed
+    pub fn add_tag(&mut self, id: ObjectId, tag: &str) -> bool {
+        let Some(obj) = self.game_objects.get_mut(id) else {
+            return false;
+        };
+        if obj.obj.add_tag(tag) {
+            self.tag_index.add_tag(id, tag);
+        }
+        true
+    }
+    /// `false` if the object was already removed
+    pub fn remove_tag(&mut self, id: ObjectId, tag: &str) -> bool {
+        let Some(obj) = self.game_objects.get_mut(id) else {
+            return false;
+        };
+        obj.obj.remove_tag(tag);
+        self.tag_index.remove_tag(id, tag);
+        true
     }
 
     pub fn render(&mut self) {
-        let screen_width: i32 = self.rl.get_screen_width();
-        let screen_height: i32 = self.rl.get_screen_height();
+        let (screen_width, screen_height): (i32, i32) = self.backend.get_screen_size();
         let grid_dimensions: &(usize, usize) = &self.space_partitioning_grid_size;
 
         let display_info: Vec<String> = self.get_display_info();
 
-        let mut d = self.rl.begin_drawing(&self.rl_thread);
-        d.clear_background(Color::WHITESMOKE);
+        let mut commands: Vec<DrawCommand> = vec![DrawCommand::Clear(Color::WHITESMOKE)];
 
-        // display objects
-        for obj in &self.game_objects {
-            obj.render(&mut d);
+        // display objects between the last two physics steps
+        let alpha: f32 = self.time_step.get_alpha();
+        for obj in self.game_objects.values() {
+            obj.render(&mut commands, alpha);
+        }
+        for joint in self.joints.values() {
+            let Some(a) = self.game_objects.get(joint.a) else {
+                continue;
+            };
+            let transform_b: Option<(Vector2, f32)> = joint
+                .get_object_b()
+                .and_then(|b| self.game_objects.get(b))
+                .map(|b| b.get_interpolated_transform(alpha));
+            joint.render(
+                &mut commands,
+                a.get_interpolated_transform(alpha),
+                transform_b,
+            );
         }
         // display grid
         for i in 0..grid_dimensions.0 {
             let y: f32 = i as f32 / grid_dimensions.0 as f32 * screen_height as f32;
-            d.draw_line(0, y as i32, screen_width, y as i32, Color::RED);
+            commands.push(DrawCommand::Line {
+                start: Vector2::new(0., y),
+                end: Vector2::new(screen_width as f32, y),
+                thickness: 1.,
+                color: Color::RED,
+            });
         }
         for i in 0..grid_dimensions.1 {
             let x: f32 = i as f32 / grid_dimensions.1 as f32 * screen_width as f32;
-            d.draw_line(x as i32, 0, x as i32, screen_height, Color::RED);
+            commands.push(DrawCommand::Line {
+                start: Vector2::new(x, 0.),
+                end: Vector2::new(x, screen_height as f32),
+                thickness: 1.,
+                color: Color::RED,
+            });
         }
 
         // display info text
-        for i in 0..display_info.len() {
-            let s: &str = &display_info[i];
-            d.draw_text(s, screen_width - 300, i as i32 * 40, 30, Color::DARKBLUE);
+        for (i, s) in display_info.into_iter().enumerate() {
+            commands.push(DrawCommand::Text {
+                text: s,
+                x: screen_width - 300,
+                y: i as i32 * 40,
+                size: 30,
+                color: Color::DARKBLUE,
+            });
         }
         // display fps
-        d.draw_fps(screen_width - 100, screen_height - 30);
+        commands.push(DrawCommand::Fps {
+            x: screen_width - 100,
+            y: screen_height - 30,
+        });
+
+        self.backend.draw(&commands);
     }
 
     pub fn get_display_info(&self) -> Vec<String> {
         let mut result: Vec<String> = Vec::new();
         result.push(format!("Object count: {}", self.game_objects.len()));
+        result.push(format!("Sleeping: {}", self.get_sleeping_count()));
         result.push(format!("Grid size: {:?}", self.space_partitioning_grid_size));
         result
     }
-    pub fn add_game_object(&mut self, game_object: PhysicsObject) {
-        self.game_objects.push(game_object);
+    pub fn add_game_object(&mut self, game_object: PhysicsObject) -> ObjectId {
+        let id: ObjectId = self.game_objects.insert(game_object);
+        if let Some(obj) = self.game_objects.get(id) {
+            self.tag_index.insert(id, &obj.obj);
+        }
+        id
+    }
+    /// `None` if the object was already removed
+    pub fn remove_game_object(&mut self, id: ObjectId) -> Option<PhysicsObject> {
+        let obj: PhysicsObject = self.game_objects.remove(id)?;
+        self.tag_index.remove(id, &obj.obj);
+        let attached_joints: Vec<ObjectId> = self
+            .joints
+            .iter()
+            .filter(|(_, joint)| joint.involves(id))
+            .map(|(joint_id, _)| joint_id)
+            .collect();
+        for joint_id in attached_joints {
+            self.remove_joint(joint_id);
+        }
+        // objects resting on the removed one have to fall down
+        for (i, j) in self.contact_tracker.get_touching_pairs() {
+            let other: ObjectId = if i == id {
+                j
+            } else if j == id {
+                i
+            } else {
+                continue;
+            };
+            if let Some(other_obj) = self.game_objects.get_mut(other) {
+                other_obj.physics.wake_up();
+            }
+        }
+        Some(obj)
+    }
+    pub fn get_game_object(&self, id: ObjectId) -> Option<&PhysicsObject> {
+        self.game_objects.get(id)
+    }
+    pub fn get_game_object_mut(&mut self, id: ObjectId) -> Option<&mut PhysicsObject> {
+        self.game_objects.get_mut(id)
+    }
+    /// all objects, added and removed with `add_game_object` and `remove_game_object`
+    pub fn get_game_objects(&self) -> &ObjectStore<PhysicsObject> {
+        &self.game_objects
+    }
+    pub fn iter_game_objects_mut(
+        &mut self,
+    ) -> impl Iterator<Item = (ObjectId, &mut PhysicsObject)> {
+        self.game_objects.iter_mut()
+    }
+    pub fn get_tag_index(&self) -> &TagIndex {
+        &self.tag_index
+    }
+    /// all joints, added and removed with `add_joint` and `remove_joint`
+    pub fn get_joints(&self) -> &ObjectStore<Joint> {
+        &self.joints
+    }
+    /// wakes the joined objects, so a sleeping object reacts to the new joint
+    pub fn add_joint(&mut self, joint: Joint) -> ObjectId {
+        self.wake_objects(&joint.get_object_ids());
+        self.joints.insert(joint)
+    }
+    /// `None` if the joint was already removed, also happens when one of its objects was removed
+    pub fn remove_joint(&mut self, id: ObjectId) -> Option<Joint> {
+        let joint: Joint = self.joints.remove(id)?;
+        self.wake_objects(&joint.get_object_ids());
+        Some(joint)
+    }
+    pub fn get_joint(&self, id: ObjectId) -> Option<&Joint> {
+        self.joints.get(id)
+    }
+    pub fn get_joint_mut(&mut self, id: ObjectId) -> Option<&mut Joint> {
+        self.joints.get_mut(id)
+    }
+    /// changes or adds the motor of a joint and wakes its objects up, `false` if the joint
+    /// doesn't exist or can't have a motor
+    pub fn set_joint_motor(&mut self, id: ObjectId, speed: f32, max_force: f32) -> bool {
+        let Some(joint) = self.joints.get_mut(id) else {
+            return false;
+        };
+        if !joint.set_motor(speed, max_force) {
+            return false;
+        }
+        let ids: Vec<ObjectId> = joint.get_object_ids();
+        self.wake_objects(&ids);
+        true
+    }
+    fn wake_objects(&mut self, ids: &[ObjectId]) {
+        for &id in ids {
+            if let Some(obj) = self.game_objects.get_mut(id) {
+                obj.physics.wake_up();
+            }
+        }
     }
 
     pub fn mouse_pos(&self) -> Vector2 {
-        self.rl.get_mouse_position()
+        self.backend.mouse_pos()
     }
 
     pub fn get_key_direction(&self) -> Vector2 {
         let mut result: Vector2 = Vector2::zero();
-        if self.rl.is_key_down(KEY_LEFT) {
+        if self.backend.is_key_down(KeyboardKey::Left) {
             result += Vector2::new(-1., 0.);
         }
-        if self.rl.is_key_down(KEY_RIGHT) {
+        if self.backend.is_key_down(KeyboardKey::Right) {
             result += Vector2::new(1., 0.);
         }
-        if self.rl.is_key_down(KEY_UP) {
+        if self.backend.is_key_down(KeyboardKey::Up) {
             result += Vector2::new(0., -1.);
         }
-        if self.rl.is_key_down(KEY_DOWN) {
+        if self.backend.is_key_down(KeyboardKey::Down) {
             result += Vector2::new(0., 1.);
         }
         result
     }
 
     pub fn mouse_clicked(&self) -> bool {
-        self.rl.is_mouse_button_pressed(MOUSE_BUTTON_LEFT)
+        self.backend.is_mouse_button_pressed(MouseButton::Left)
+    }
+    /// `true` as long as the left mouse button is held
+    pub fn mouse_down(&self) -> bool {
+        self.backend.is_mouse_button_down(MouseButton::Left)
     }
     pub fn get_frame_time(&self) -> f32 {
-        self.rl.get_frame_time()
+        self.backend.get_frame_time()
     }
     pub fn get_run_time(&self) -> f32 {
-        self.rl.get_time() as f32
+        self.backend.get_run_time()
     }
 
     pub fn get_random_value<T: From<i32>>(&self, num: Range<i32>) -> T {
-        self.rl.get_random_value(num)
+        rand::rng().random_range(num).into()
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rust_game_engine::physics::contact_events::ContactEventKind;
+    use crate::rust_game_engine::physics::material::Material;
+
+    #[test]
+    fn falling_object_comes_to_rest_on_the_ground() {
+        let mut scene: Scene = Scene::new_headless(1. / 60.);
+        for mut wall in PhysicsObject::generate_walls() {
+            // the default material bounces for a long time
+            wall.material = Material::METAL;
+            scene.add_game_object(wall);
+        }
+        let ball: PhysicsObject = PhysicsObject::new_circle(
+            Vector2::new(500., 100.),
+            20.,
+            Material::METAL,
+            "ball".to_string(),
+        );
+        let id: ObjectId = scene.add_game_object(ball);
+
+        for _ in 0..600 {
+            scene.backend.begin_frame();
+            let delta_time: f32 = scene.get_frame_time();
+            scene.frame_logic(delta_time);
+        }
+
+        let ball: &PhysicsObject = scene.get_game_object(id).unwrap();
+        // resting on the bottom wall
+        assert!(
+            (ball.obj.pos.y - (HEIGHT_F - 20.)).abs() < 2.,
+            "ball at {}",
+            ball.obj.pos.y
+        );
+        assert!(ball.physics.get_vel().length() < 1.);
+        assert!(ball.is_sleeping());
+    }
+
+    #[test]
+    fn sleeping_contact_persists_until_woken() {
+        let mut scene: Scene = Scene::new_headless(1. / 60.);
+        for mut wall in PhysicsObject::generate_walls() {
+            wall.material = Material::METAL;
+            scene.add_game_object(wall);
+        }
+        let ball: PhysicsObject = PhysicsObject::new_circle(
+            Vector2::new(500., HEIGHT_F - 40.),
+            20.,
+            Material::METAL,
+            "ball".to_string(),
+        );
+        let id: ObjectId = scene.add_game_object(ball);
+        for _ in 0..300 {
+            scene.frame_logic(1. / 60.);
+        }
+        assert!(scene.get_game_object(id).unwrap().is_sleeping());
+
+        scene.frame_logic(1. / 60.);
+        let kinds: Vec<ContactEventKind> = scene
+            .contact_events
+            .iter()
+            .filter(|event| event.involves(id))
+            .map(|event| event.kind)
+            .collect();
+        assert_eq!(kinds, vec![ContactEventKind::Persist]);
+
+        let ball: &mut PhysicsObject = scene.get_game_object_mut(id).unwrap();
+        *ball.physics.get_vel_mut().unwrap() = Vector2::new(0., -300.);
+        scene.frame_logic(1. / 60.);
+        let kinds: Vec<ContactEventKind> = scene
+            .contact_events
+            .iter()
+            .filter(|event| event.involves(id))
+            .map(|event| event.kind)
+            .collect();
+        assert_eq!(kinds, vec![ContactEventKind::End]);
+    }
+
+    #[test]
+    fn kinematic_object_pushes_without_being_pushed_back() {
+        let mut scene: Scene = Scene::new_headless(1. / 60.);
+        scene.disable_gravity();
+        let pusher: PhysicsObject = PhysicsObject::new_circle(
+            Vector2::new(300., 300.),
+            30.,
+            Material::METAL,
+            "pusher".to_string(),
+        )
+        .into_kinematic();
+        let pusher: ObjectId = scene.add_game_object(pusher);
+        let vel: Vector2 = Vector2::new(200., 0.);
+        *scene
+            .get_game_object_mut(pusher)
+            .unwrap()
+            .physics
+            .get_vel_mut()
+            .unwrap() = vel;
+        // heavy, so that a pushed back pusher would be easy to notice
+        let ball: PhysicsObject = PhysicsObject::new_circle(
+            Vector2::new(400., 300.),
+            30.,
+            Material::METAL,
+            "ball".to_string(),
+        );
+        let ball: ObjectId = scene.add_game_object(ball);
+
+        for _ in 0..60 {
+            scene.frame_logic(1. / 60.);
+        }
+
+        let pusher: &PhysicsObject = scene.get_game_object(pusher).unwrap();
+        assert_eq!(pusher.physics.get_vel(), vel);
+        assert!(
+            (pusher.obj.pos.x - 500.).abs() < 0.01,
+            "pusher at {}",
+            pusher.obj.pos.x
+        );
+        assert!((pusher.obj.pos.y - 300.).abs() < 0.01);
+        let ball: &PhysicsObject = scene.get_game_object(ball).unwrap();
+        assert!(
+            ball.obj.pos.x > pusher.obj.pos.x + 59.,
+            "ball at {}",
+            ball.obj.pos.x
+        );
+        assert!(ball.physics.get_vel().x >= vel.x * 0.99);
+    }
+
+    #[test]
+    fn tags_and_names_changed_through_the_scene() {
+        let mut scene: Scene = Scene::new_headless(1. / 60.);
+        let mut ids: Vec<ObjectId> = vec![];
+        for i in 0..4 {
+            let obj: PhysicsObject = PhysicsObject::new_circle(
+                Vector2::new(100. * i as f32, 0.),
+                10.,
+                Material::default(),
+                "obj".to_string(),
+            );
+            ids.push(scene.add_game_object(obj.with_tag("enemy")));
+        }
+        scene.remove_tag(ids[1], "enemy");
+        scene.remove_game_object(ids[2]);
+        scene.add_tag(ids[0], "boss");
+        scene.set_name_tag(ids[3], "last");
+
+        let enemies: Vec<ObjectId> = scene.iter_with_tag_mut("enemy").map(|(id, _)| id).collect();
+        assert_eq!(enemies, vec![ids[0], ids[3]]);
+        let boss: &PhysicsObject = scene.iter_with_tag("boss").next().unwrap().1;
+        assert_eq!(boss.obj.get_tags(), ["enemy", "boss"]);
+        assert_eq!(scene.get_id_by_name("last"), Some(ids[3]));
+        assert_eq!(scene.get_id_by_name("obj"), Some(ids[0]));
+    }
+}
@@ -1,4 +1,5 @@
 use crate::project;
+use crate::rust_game_engine::backend::raylib_backend::RaylibBackend;
 use crate::rust_game_engine::constants::*;
 use crate::rust_game_engine::engine_core::*;
 
@@ -10,18 +11,19 @@ pub fn init_game() {
         .title("Elastic collisions")
         .build();
 
-    let mut scene = Scene::new(rl, rl_thread);
+    let mut scene = Scene::new(Box::new(RaylibBackend::new(rl, rl_thread)));
 
     user_setup(&mut scene);
 
-    while !scene.rl.window_should_close() {
+    while !scene.backend.should_close() {
         frame_logic(&mut scene);
         scene.render();
     }
 }
 
 fn frame_logic(scene: &mut Scene) {
-    let delta_time: f32 = scene.rl.get_frame_time();
+    scene.backend.begin_frame();
+    let delta_time: f32 = scene.get_frame_time();
 
     scene.frame_logic(delta_time);
     user_loop(scene, delta_time);
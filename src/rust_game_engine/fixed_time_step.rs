@@ -0,0 +1,103 @@
+use crate::rust_game_engine::constants::*;
+
+/// Splits the variable frame time into physics steps of constant length
+pub struct FixedTimeStep {
+    /// physics steps per second, always positive
+    hz: f32,
+    /// steps exceeding this in one frame are dropped, so a hitch can not make the simulation explode
+    pub max_steps_per_frame: usize,
+    /// time that has not been simulated yet
+    pub accumulator: f32,
+}
+
+impl FixedTimeStep {
+    /// `None` if `hz` is not positive
+    pub fn new(hz: f32, max_steps_per_frame: usize) -> Option<FixedTimeStep> {
+        if !(hz > 0. && hz.is_finite()) {
+            return None;
+        }
+        Some(FixedTimeStep {
+            hz,
+            max_steps_per_frame,
+            accumulator: 0.,
+        })
+    }
+
+    pub fn get_hz(&self) -> f32 {
+        self.hz
+    }
+    /// `false` and unchanged if `hz` is not positive
+    pub fn set_hz(&mut self, hz: f32) -> bool {
+        if !(hz > 0. && hz.is_finite()) {
+            return false;
+        }
+        self.hz = hz;
+        true
+    }
+
+    pub fn get_step_time(&self) -> f32 {
+        1. / self.hz
+    }
+
+    /// adds the frame time and returns how many physics steps have to run.
+    /// Whole steps above `max_steps_per_frame` are dropped, the rest of a step is kept
+    pub fn advance(&mut self, frame_time: f32) -> usize {
+        let step_time: f32 = self.get_step_time();
+        self.accumulator += frame_time;
+
+        let steps: f32 = (self.accumulator / step_time).floor().max(0.);
+        self.accumulator = (self.accumulator - steps * step_time).max(0.);
+        (steps as usize).min(self.max_steps_per_frame)
+    }
+
+    /// how far (0..1) the rendered state is between the previous and the current step
+    pub fn get_alpha(&self) -> f32 {
+        (self.accumulator / self.get_step_time()).clamp(0., 1.)
+    }
+}
+
+impl Default for FixedTimeStep {
+    fn default() -> Self {
+        FixedTimeStep {
+            hz: PHYSICS_HZ,
+            max_steps_per_frame: MAX_STEPS_PER_FRAME,
+            accumulator: 0.,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steps_are_capped_and_the_rest_of_a_step_is_kept() {
+        let mut time_step: FixedTimeStep = FixedTimeStep::new(60., 5).unwrap();
+        assert_eq!(time_step.advance(0.025), 1);
+        assert!((time_step.accumulator - (0.025 - 1. / 60.)).abs() < 1e-5);
+        assert_eq!(time_step.advance(0.01), 1);
+        assert!((time_step.accumulator - (0.035 - 2. / 60.)).abs() < 1e-5);
+
+        // a hitch of 60.6 steps only runs 5, the 0.6 steps are simulated later
+        time_step.accumulator = 0.;
+        assert_eq!(time_step.advance(1.01), 5);
+        assert!(
+            (time_step.accumulator - 0.01).abs() < 1e-4,
+            "{}",
+            time_step.accumulator
+        );
+        assert_eq!(time_step.advance(0.), 0);
+    }
+
+    #[test]
+    fn frequency_has_to_be_positive() {
+        assert!(FixedTimeStep::new(0., 5).is_none());
+        assert!(FixedTimeStep::new(-60., 5).is_none());
+        assert!(FixedTimeStep::new(f32::NAN, 5).is_none());
+        let mut time_step: FixedTimeStep = FixedTimeStep::default();
+        assert!(!time_step.set_hz(0.));
+        assert_eq!(time_step.get_hz(), PHYSICS_HZ);
+        assert!(time_step.set_hz(120.));
+        assert_eq!(time_step.get_step_time(), 1. / 120.);
+    }
+}
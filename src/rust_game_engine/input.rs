@@ -0,0 +1,22 @@
+/// Keys the backends report, named like on a US keyboard
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum KeyboardKey {
+    Left,
+    Right,
+    Up,
+    Down,
+    W,
+    A,
+    S,
+    D,
+    Space,
+    Enter,
+    Escape,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MouseButton {
+    Left,
+    Right,
+    Middle,
+}
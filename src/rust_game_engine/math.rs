@@ -0,0 +1,180 @@
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// 2d vector in px, y points down like on the screen.
+/// Same behaviour as the raylib type, so the physics don't depend on raylib
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Vector2 {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Vector2 {
+    pub const fn new(x: f32, y: f32) -> Vector2 {
+        Vector2 { x, y }
+    }
+
+    pub const fn zero() -> Vector2 {
+        Vector2::new(0., 0.)
+    }
+
+    pub fn dot(&self, v: Vector2) -> f32 {
+        self.x * v.x + self.y * v.y
+    }
+
+    pub fn length(&self) -> f32 {
+        self.length_sqr().sqrt()
+    }
+
+    pub fn length_sqr(&self) -> f32 {
+        self.x * self.x + self.y * self.y
+    }
+
+    pub fn scale(&mut self, s: f32) {
+        *self *= s;
+    }
+
+    pub fn scale_by(&self, s: f32) -> Vector2 {
+        *self * s
+    }
+
+    /// unit vector in the same direction, the zero vector stays zero
+    pub fn normalized(&self) -> Vector2 {
+        let length_sqr: f32 = self.length_sqr();
+        if length_sqr == 0. {
+            return *self;
+        }
+        *self / length_sqr.sqrt()
+    }
+
+    /// rotated by `angle` rad, clockwise on screen
+    pub fn rotated(&self, angle: f32) -> Vector2 {
+        let (sin, cos): (f32, f32) = angle.sin_cos();
+        Vector2::new(self.x * cos - self.y * sin, self.x * sin + self.y * cos)
+    }
+
+    /// `amount` 0 is this vector, 1 is `v`
+    pub fn lerp(&self, v: Vector2, amount: f32) -> Vector2 {
+        *self + (v - *self) * amount
+    }
+}
+
+impl Add for Vector2 {
+    type Output = Vector2;
+    fn add(self, v: Vector2) -> Vector2 {
+        Vector2::new(self.x + v.x, self.y + v.y)
+    }
+}
+
+impl Sub for Vector2 {
+    type Output = Vector2;
+    fn sub(self, v: Vector2) -> Vector2 {
+        Vector2::new(self.x - v.x, self.y - v.y)
+    }
+}
+
+impl Mul<f32> for Vector2 {
+    type Output = Vector2;
+    fn mul(self, s: f32) -> Vector2 {
+        Vector2::new(self.x * s, self.y * s)
+    }
+}
+
+impl Div<f32> for Vector2 {
+    type Output = Vector2;
+    fn div(self, s: f32) -> Vector2 {
+        Vector2::new(self.x / s, self.y / s)
+    }
+}
+
+impl Neg for Vector2 {
+    type Output = Vector2;
+    fn neg(self) -> Vector2 {
+        Vector2::new(-self.x, -self.y)
+    }
+}
+
+impl AddAssign for Vector2 {
+    fn add_assign(&mut self, v: Vector2) {
+        *self = *self + v;
+    }
+}
+
+impl SubAssign for Vector2 {
+    fn sub_assign(&mut self, v: Vector2) {
+        *self = *self - v;
+    }
+}
+
+impl MulAssign<f32> for Vector2 {
+    fn mul_assign(&mut self, s: f32) {
+        *self = *self * s;
+    }
+}
+
+impl DivAssign<f32> for Vector2 {
+    fn div_assign(&mut self, s: f32) {
+        *self = *self / s;
+    }
+}
+
+/// Axis aligned rectangle, `x` and `y` are the top left corner
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Rectangle {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Rectangle {
+    pub const fn new(x: f32, y: f32, width: f32, height: f32) -> Rectangle {
+        Rectangle {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// `true` if the rectangles overlap, touching edges don't count
+    pub fn check_collision_recs(&self, other: &Rectangle) -> bool {
+        self.x < other.x + other.width
+            && other.x < self.x + self.width
+            && self.y < other.y + other.height
+            && other.y < self.y + self.height
+    }
+
+    /// the left and top edges are inside, the right and bottom ones outside
+    pub fn check_collision_point_rec(&self, point: Vector2) -> bool {
+        point.x >= self.x
+            && point.x < self.x + self.width
+            && point.y >= self.y
+            && point.y < self.y + self.height
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotation_is_clockwise_on_screen() {
+        let v: Vector2 = Vector2::new(1., 0.).rotated(std::f32::consts::FRAC_PI_2);
+        assert!((v - Vector2::new(0., 1.)).length() < 1e-6);
+    }
+
+    #[test]
+    fn zero_vector_stays_zero_when_normalized() {
+        assert_eq!(Vector2::zero().normalized(), Vector2::zero());
+        assert!((Vector2::new(3., 4.).normalized().length() - 1.).abs() < 1e-6);
+    }
+
+    #[test]
+    fn rectangles_touching_at_an_edge_do_not_collide() {
+        let a: Rectangle = Rectangle::new(0., 0., 10., 10.);
+        assert!(a.check_collision_recs(&Rectangle::new(5., 5., 10., 10.)));
+        assert!(!a.check_collision_recs(&Rectangle::new(10., 0., 10., 10.)));
+        assert!(a.check_collision_point_rec(Vector2::new(0., 0.)));
+        assert!(!a.check_collision_point_rec(Vector2::new(10., 5.)));
+    }
+}
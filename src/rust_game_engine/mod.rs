@@ -1,5 +1,13 @@
+pub mod backend;
+pub mod color;
 pub mod constants;
+#[cfg(feature = "raylib")]
 pub mod engine_init;
 pub mod engine_core;
+pub mod fixed_time_step;
+pub mod input;
+pub mod math;
+pub mod object_store;
 pub mod physics;
+pub mod tag_index;
 pub mod timer;
\ No newline at end of file
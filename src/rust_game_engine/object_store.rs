@@ -0,0 +1,205 @@
+/// Handle to an object in an `ObjectStore`.
+/// Stays valid when other objects are added or removed, and becomes stale once its object is removed
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ObjectId {
+    pub index: usize,
+    pub generation: u32,
+}
+
+struct Slot<T> {
+    /// increased every time the value is removed, so that old ids don't match a reused slot
+    generation: u32,
+    value: Option<T>,
+}
+
+/// Slot map: objects are stored in reusable slots and addressed by generational ids
+pub struct ObjectStore<T> {
+    slots: Vec<Slot<T>>,
+    free_indices: Vec<usize>,
+    len: usize,
+}
+
+impl<T> ObjectStore<T> {
+    pub fn new() -> ObjectStore<T> {
+        ObjectStore {
+            slots: vec![],
+            free_indices: vec![],
+            len: 0,
+        }
+    }
+
+    pub fn insert(&mut self, value: T) -> ObjectId {
+        self.len += 1;
+        if let Some(index) = self.free_indices.pop() {
+            let slot: &mut Slot<T> = &mut self.slots[index];
+            slot.value = Some(value);
+            return ObjectId {
+                index,
+                generation: slot.generation,
+            };
+        }
+
+        self.slots.push(Slot {
+            generation: 0,
+            value: Some(value),
+        });
+        ObjectId {
+            index: self.slots.len() - 1,
+            generation: 0,
+        }
+    }
+
+    /// `None` if the id is stale
+    pub fn remove(&mut self, id: ObjectId) -> Option<T> {
+        let slot: &mut Slot<T> = self.slots.get_mut(id.index)?;
+        if slot.generation != id.generation {
+            return None;
+        }
+        let value: T = slot.value.take()?;
+
+        slot.generation += 1;
+        self.free_indices.push(id.index);
+        self.len -= 1;
+        Some(value)
+    }
+
+    pub fn contains(&self, id: ObjectId) -> bool {
+        self.get(id).is_some()
+    }
+
+    pub fn get(&self, id: ObjectId) -> Option<&T> {
+        let slot: &Slot<T> = self.slots.get(id.index)?;
+        if slot.generation != id.generation {
+            return None;
+        }
+        slot.value.as_ref()
+    }
+
+    pub fn get_mut(&mut self, id: ObjectId) -> Option<&mut T> {
+        let slot: &mut Slot<T> = self.slots.get_mut(id.index)?;
+        if slot.generation != id.generation {
+            return None;
+        }
+        slot.value.as_mut()
+    }
+
+    /// mutable references to two different objects, `None` if an id is stale or both are the same
+    pub fn get_pair_mut(&mut self, a: ObjectId, b: ObjectId) -> Option<(&mut T, &mut T)> {
+        if a.index == b.index || !self.contains(a) || !self.contains(b) {
+            return None;
+        }
+
+        let (low, high): (usize, usize) = (a.index.min(b.index), a.index.max(b.index));
+        let (left, right) = self.slots.split_at_mut(high);
+        let low_value: &mut T = left[low].value.as_mut()?;
+        let high_value: &mut T = right[0].value.as_mut()?;
+
+        if a.index < b.index {
+            Some((low_value, high_value))
+        } else {
+            Some((high_value, low_value))
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// all live objects, ordered by slot
+    pub fn iter(&self) -> impl Iterator<Item = (ObjectId, &T)> {
+        self.slots.iter().enumerate().filter_map(|(index, slot)| {
+            let id: ObjectId = ObjectId {
+                index,
+                generation: slot.generation,
+            };
+            Some((id, slot.value.as_ref()?))
+        })
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (ObjectId, &mut T)> {
+        self.slots
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(index, slot)| {
+                let id: ObjectId = ObjectId {
+                    index,
+                    generation: slot.generation,
+                };
+                Some((id, slot.value.as_mut()?))
+            })
+    }
+
+    /// objects of `ids`, which have to be sorted by index like the ids of a `BTreeSet`.
+    /// Only the slots of these ids are visited, stale ids are skipped
+    pub fn iter_mut_sorted<'a>(
+        &'a mut self,
+        ids: impl Iterator<Item = ObjectId> + 'a,
+    ) -> impl Iterator<Item = (ObjectId, &'a mut T)> + 'a {
+        let mut slots = self.slots.iter_mut();
+        let mut next_index: usize = 0;
+        ids.filter_map(move |id| {
+            // an index before the last visited slot can't be reached any more
+            if id.index < next_index {
+                return None;
+            }
+            let slot: &mut Slot<T> = slots.nth(id.index - next_index)?;
+            next_index = id.index + 1;
+            if slot.generation != id.generation {
+                return None;
+            }
+            Some((id, slot.value.as_mut()?))
+        })
+    }
+
+    pub fn ids(&self) -> impl Iterator<Item = ObjectId> + '_ {
+        self.iter().map(|(id, _)| id)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.slots.iter().filter_map(|slot| slot.value.as_ref())
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.slots.iter_mut().filter_map(|slot| slot.value.as_mut())
+    }
+}
+
+impl<T> Default for ObjectStore<T> {
+    fn default() -> Self {
+        ObjectStore::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn removed_id_returns_none() {
+        let mut store: ObjectStore<&str> = ObjectStore::new();
+        let a: ObjectId = store.insert("a");
+        let b: ObjectId = store.insert("b");
+        assert_eq!(store.remove(a), Some("a"));
+        assert_eq!(store.get(a), None);
+        assert_eq!(store.remove(a), None);
+        assert_eq!(store.get(b), Some(&"b"));
+        assert_eq!(store.len(), 1);
+    }
+
+    #[test]
+    fn reused_slot_does_not_resolve_the_old_id() {
+        let mut store: ObjectStore<&str> = ObjectStore::new();
+        let old: ObjectId = store.insert("old");
+        store.remove(old);
+        let new: ObjectId = store.insert("new");
+        assert_eq!(new.index, old.index);
+        assert_eq!(store.get(old), None);
+        assert_eq!(store.get_mut(old), None);
+        assert_eq!(store.remove(old), None);
+        assert_eq!(store.get(new), Some(&"new"));
+    }
+}
@@ -0,0 +1,344 @@
+use crate::rust_game_engine::constants::*;
+use crate::rust_game_engine::engine_core::Scene;
+use crate::rust_game_engine::math::{Rectangle, Vector2};
+use crate::rust_game_engine::object_store::ObjectId;
+use crate::rust_game_engine::physics::collision::project;
+use crate::rust_game_engine::physics::game_object::PhysicsObject;
+use crate::rust_game_engine::physics::shape::Shape;
+use std::collections::HashMap;
+
+/// First touch of a swept object
+#[derive(Clone, Copy, Debug)]
+pub struct Impact {
+    /// fraction (0..1) of the movement before the touch
+    pub t: f32,
+    /// unit normal pointing from the moving object to the one it hit
+    pub normal: Vector2,
+}
+
+impl Scene {
+    /// moves fast objects with ccd back to where they first hit another object during the last move,
+    /// so that they can't pass through thin walls. `start_positions` are the positions before the move
+    /// of all objects that moved, the targets are swept along their own movement too.
+    /// Returns the impact of every clamped object with `t` set to the fraction of the move that is left,
+    /// see `continue_after_impact`. The rotation during the move is ignored
+    pub fn clamp_to_time_of_impact(
+        &mut self,
+        start_positions: &[(ObjectId, Vector2)],
+    ) -> Vec<(ObjectId, Impact)> {
+        let start_by_id: HashMap<ObjectId, Vector2> = start_positions.iter().copied().collect();
+        let mut remaining: Vec<(ObjectId, Impact)> = Vec::new();
+        for &(id, start_pos) in start_positions {
+            let Some(obj) = self.game_objects.get(id) else {
+                continue;
+            };
+            if !obj.ccd_enabled || !obj.physics.is_dynamic() {
+                continue;
+            }
+            let movement: Vector2 = obj.obj.pos - start_pos;
+            let distance: f32 = movement.length();
+            if distance <= PENETRATION_SLOP {
+                continue;
+            }
+            let swept_box: Rectangle = get_swept_bounding_box(obj, movement);
+
+            let mut first_impact: Option<Impact> = None;
+            for (other_id, other) in self.game_objects.iter() {
+                let other_movement: Vector2 = start_by_id
+                    .get(&other_id)
+                    .map_or(Vector2::zero(), |&other_start| other.obj.pos - other_start);
+                let can_hit: bool = other_id != id
+                    && !other.is_sensor
+                    && obj.collision_filter.should_collide(&other.collision_filter)
+                    && !self.is_joined_without_collision(id, other_id)
+                    && swept_box
+                        .check_collision_recs(&get_swept_bounding_box(other, other_movement));
+                if !can_hit {
+                    continue;
+                }
+                // in the frame of the other object
+                let impact: Option<Impact> =
+                    get_time_of_impact(obj, movement - other_movement, other);
+                first_impact = get_first(first_impact, impact);
+            }
+            let Some(first_impact) = first_impact else {
+                continue;
+            };
+
+            // slightly past the impact, so that the narrow phase finds the contact
+            let t: f32 = (first_impact.t + PENETRATION_SLOP / distance).min(1.);
+            let target: Vector2 = start_pos + movement * t;
+            if let Some(obj) = self.game_objects.get_mut(id) {
+                let offset: Vector2 = target - obj.obj.pos;
+                obj.correct_position(&offset);
+            }
+            remaining.push((
+                id,
+                Impact {
+                    t: 1. - t,
+                    ..first_impact
+                },
+            ));
+        }
+        remaining
+    }
+
+    /// moves the objects clamped by `clamp_to_time_of_impact` for the rest of the sub step with the
+    /// velocity after the contact was solved, but not further into the object they hit, e.g. when
+    /// a spinning object still moves into a wall. The second move is clamped again,
+    /// what is left after a second impact is dropped
+    pub fn continue_after_impact(&mut self, remaining: &[(ObjectId, Impact)], delta_time: f32) {
+        let mut start_positions: Vec<(ObjectId, Vector2)> = Vec::new();
+        for &(id, impact) in remaining {
+            let Some(obj) = self.game_objects.get_mut(id) else {
+                continue;
+            };
+            let mut vel: Vector2 = obj.physics.get_vel();
+            let into_impact: f32 = vel.dot(impact.normal);
+            if into_impact > 0. {
+                vel -= impact.normal * into_impact;
+            }
+            start_positions.push((id, obj.obj.pos));
+            let offset: Vector2 = vel * (impact.t * delta_time);
+            obj.correct_position(&offset);
+        }
+        self.clamp_to_time_of_impact(&start_positions);
+    }
+
+    /// joints that turn off the collision between their objects, like the broad phase
+    fn is_joined_without_collision(&self, i: ObjectId, j: ObjectId) -> bool {
+        self.joints
+            .values()
+            .any(|joint| !joint.collide_connected && joint.involves(i) && joint.involves(j))
+    }
+}
+
+/// bounding box of the object over the whole movement that ended at its current position
+fn get_swept_bounding_box(obj: &PhysicsObject, movement: Vector2) -> Rectangle {
+    let bb: Rectangle = obj.get_bounding_box();
+    let min_x: f32 = bb.x.min(bb.x - movement.x);
+    let min_y: f32 = bb.y.min(bb.y - movement.y);
+    Rectangle::new(
+        min_x,
+        min_y,
+        bb.width + movement.x.abs(),
+        bb.height + movement.y.abs(),
+    )
+}
+
+/// swept separating axis test, rotation during the movement is ignored.
+/// Objects that already touch at the start hit at 0 if the movement goes deeper,
+/// `None` if they don't touch or move apart
+fn get_time_of_impact(
+    obj: &PhysicsObject,
+    movement: Vector2,
+    other: &PhysicsObject,
+) -> Option<Impact> {
+    let mut first_impact: Option<Impact> = None;
+    for piece in obj.get_pieces() {
+        for other_piece in other.get_pieces() {
+            let impact: Option<Impact> = get_piece_time_of_impact(piece, movement, other_piece);
+            first_impact = get_first(first_impact, impact);
+        }
+    }
+    first_impact
+}
+
+fn get_first(a: Option<Impact>, b: Option<Impact>) -> Option<Impact> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(if b.t < a.t { b } else { a }),
+        _ => a.or(b),
+    }
+}
+
+/// like `get_time_of_impact` for two convex shapes
+fn get_piece_time_of_impact(shape: &Shape, movement: Vector2, other: &Shape) -> Option<Impact> {
+    let start_points: Vec<Vector2> = shape
+        .get_core_points()
+        .iter()
+        .map(|&c| c - movement)
+        .collect();
+    let other_points: Vec<Vector2> = other.get_core_points();
+    let (radius, other_radius): (f32, f32) = (shape.get_radius(), other.get_radius());
+
+    let mut u_axes: Vec<Vector2> = shape.get_face_normals();
+    u_axes.extend(other.get_face_normals());
+    if radius > 0. || other_radius > 0. {
+        // round shapes can also hit corners, their direction is taken at the end of the movement
+        for &point in &shape.get_core_points() {
+            for &other_point in &other_points {
+                let dir: Vector2 = other_point - point;
+                if dir.length_sqr() > f32::EPSILON {
+                    u_axes.push(dir.normalized());
+                }
+            }
+        }
+    }
+
+    let mut t_enter: f32 = f32::NEG_INFINITY;
+    let mut t_exit: f32 = f32::INFINITY;
+    let mut normal: Vector2 = Vector2::zero();
+    // axis of the smallest overlap at the start, the contact normal if they already touch
+    let mut start_overlap: f32 = f32::INFINITY;
+    let mut start_normal: Vector2 = Vector2::zero();
+    for u_axis in u_axes {
+        let (self_min, self_max) = project(&start_points, radius, u_axis);
+        let (other_min, other_max) = project(&other_points, other_radius, u_axis);
+        let speed: f32 = movement.dot(u_axis);
+
+        let (overlap_ahead, overlap_behind): (f32, f32) =
+            (self_max - other_min, other_max - self_min);
+        if overlap_ahead.min(overlap_behind) < start_overlap {
+            start_overlap = overlap_ahead.min(overlap_behind);
+            start_normal = if overlap_ahead < overlap_behind {
+                u_axis
+            } else {
+                -u_axis
+            };
+        }
+
+        if speed == 0. {
+            // never overlapping on this axis
+            if self_max < other_min || other_max < self_min {
+                return None;
+            }
+            continue;
+        }
+        let t0: f32 = (other_min - self_max) / speed;
+        let t1: f32 = (other_max - self_min) / speed;
+        if t0.min(t1) > t_enter {
+            t_enter = t0.min(t1);
+            normal = if speed > 0. { u_axis } else { -u_axis };
+        }
+        t_exit = t_exit.min(t0.max(t1));
+        if t_enter > t_exit {
+            return None;
+        }
+    }
+
+    if t_enter < 0. && t_exit >= 0. {
+        return (movement.dot(start_normal) > 0.).then_some(Impact {
+            t: 0.,
+            normal: start_normal,
+        });
+    }
+    (0. ..=1.)
+        .contains(&t_enter)
+        .then_some(Impact { t: t_enter, normal })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rust_game_engine::physics::material::Material;
+    use crate::rust_game_engine::physics::physics_addition::Polygon;
+
+    #[test]
+    fn bullet_does_not_pass_through_thin_wall() {
+        let mut scene: Scene = Scene::new_headless(1. / 60.);
+        scene.disable_gravity();
+        for wall in PhysicsObject::generate_walls() {
+            scene.add_game_object(wall);
+        }
+        // moves much further than the 1 px thick right wall in a single sub step
+        let bullet: PhysicsObject = PhysicsObject::new_circle(
+            Vector2::new(WIDTH_F - 100., 300.),
+            2.,
+            Material::default(),
+            "bullet".to_string(),
+        )
+        .with_ccd();
+        let id: ObjectId = scene.add_game_object(bullet);
+        if let Some(vel) = scene
+            .get_game_object_mut(id)
+            .and_then(|obj| obj.physics.get_vel_mut())
+        {
+            *vel = Vector2::new(30000., 0.);
+        }
+
+        for _ in 0..10 {
+            scene.physics_step(1. / 60.);
+        }
+        let pos: Vector2 = scene.get_game_object(id).unwrap().obj.pos;
+        assert!(
+            0. < pos.x && pos.x < WIDTH_F,
+            "bullet passed through a wall at {}",
+            pos.x
+        );
+    }
+
+    #[test]
+    fn bullet_bounces_back_in_the_step_it_hits() {
+        let mut scene: Scene = Scene::new_headless(1. / 60.);
+        scene.disable_gravity();
+        for wall in PhysicsObject::generate_walls() {
+            scene.add_game_object(wall);
+        }
+        let bullet: PhysicsObject = PhysicsObject::new_circle(
+            Vector2::new(WIDTH_F - 100., 300.),
+            2.,
+            Material::default(),
+            "bullet".to_string(),
+        )
+        .with_ccd();
+        let id: ObjectId = scene.add_game_object(bullet);
+        if let Some(vel) = scene
+            .get_game_object_mut(id)
+            .and_then(|obj| obj.physics.get_vel_mut())
+        {
+            *vel = Vector2::new(30000., 0.);
+        }
+
+        scene.physics_step(1. / 60.);
+        let bullet: &PhysicsObject = scene.get_game_object(id).unwrap();
+        assert!(bullet.physics.get_vel().x < 0.);
+        // the rest of the move after the impact goes back towards the start
+        assert!(
+            bullet.obj.pos.x < WIDTH_F - 100.,
+            "bullet at {}",
+            bullet.obj.pos.x
+        );
+    }
+
+    #[test]
+    fn bullet_hits_a_thin_dynamic_plank() {
+        let mut scene: Scene = Scene::new_headless(1. / 60.);
+        scene.disable_gravity();
+        let plank: PhysicsObject = PhysicsObject::new_with_shape(
+            Vector2::new(500., 300.),
+            Shape::Polygon(Polygon::new_polygon_line(
+                Vector2::new(500., 250.),
+                Vector2::new(500., 350.),
+                1.,
+            )),
+            Material::default(),
+            "plank".to_string(),
+        );
+        let plank_id: ObjectId = scene.add_game_object(plank);
+        let bullet: PhysicsObject = PhysicsObject::new_circle(
+            Vector2::new(100., 300.),
+            2.,
+            Material::default(),
+            "bullet".to_string(),
+        )
+        .with_ccd();
+        let id: ObjectId = scene.add_game_object(bullet);
+        if let Some(vel) = scene
+            .get_game_object_mut(id)
+            .and_then(|obj| obj.physics.get_vel_mut())
+        {
+            *vel = Vector2::new(30000., 0.);
+        }
+
+        for _ in 0..2 {
+            scene.physics_step(1. / 60.);
+        }
+        let plank: &PhysicsObject = scene.get_game_object(plank_id).unwrap();
+        let bullet: &PhysicsObject = scene.get_game_object(id).unwrap();
+        assert!(
+            bullet.obj.pos.x < plank.obj.pos.x,
+            "bullet passed the plank"
+        );
+        assert!(plank.physics.get_vel().x > 0.);
+    }
+}
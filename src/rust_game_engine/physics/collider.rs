@@ -0,0 +1,95 @@
+use crate::rust_game_engine::backend::DrawCommand;
+use crate::rust_game_engine::color::Color;
+use crate::rust_game_engine::math::Vector2;
+use crate::rust_game_engine::physics::shape::Shape;
+
+/// One shape of an object, placed relative to the object position and rotation
+pub struct Collider {
+    pub shape: Shape,
+    /// position relative to the object position, before the object rotation
+    pub offset: Vector2,
+    /// rotation relative to the object rotation
+    pub rotation: f32,
+}
+
+impl Collider {
+    /// the shape is placed at the object position, its own position is ignored
+    pub fn new(shape: Shape) -> Collider {
+        Collider {
+            shape,
+            offset: Vector2::zero(),
+            rotation: 0.,
+        }
+    }
+
+    /// e.g. `Collider::new(Shape::new_circle(Vector2::zero(), 10.)).with_offset(Vector2::new(40., 0.), 0.)`
+    pub fn with_offset(mut self, offset: Vector2, rotation: f32) -> Collider {
+        self.offset = offset;
+        self.rotation = rotation;
+        self
+    }
+
+    /// position and rotation in world space for the given object transform
+    pub fn get_transform(&self, pos: Vector2, rotation: f32) -> (Vector2, f32) {
+        (
+            pos + self.offset.rotated(rotation),
+            rotation + self.rotation,
+        )
+    }
+
+    /// recomputes the world space geometry of the shape from the object transform
+    pub fn update_transform(&mut self, pos: Vector2, rotation: f32) {
+        let (pos, rotation) = self.get_transform(pos, rotation);
+        self.shape.update_transform(pos, rotation);
+    }
+
+    /// center of the area relative to the object position, before the object rotation
+    pub fn get_local_centroid(&self) -> Vector2 {
+        self.offset + self.shape.get_centroid().rotated(self.rotation)
+    }
+
+    /// draws the shape for the given object transform
+    pub fn render(
+        &self,
+        commands: &mut Vec<DrawCommand>,
+        pos: Vector2,
+        rotation: f32,
+        color: Color,
+    ) {
+        let (pos, rotation) = self.get_transform(pos, rotation);
+        self.shape.render(commands, pos, rotation, color);
+    }
+}
+
+/// mass, center of mass relative to the object position and moment of inertia around the
+/// center of mass of colliders with a uniform density
+pub fn get_mass_properties(colliders: &[Collider], density: f32) -> (f32, Vector2, f32) {
+    let masses: Vec<f32> = colliders
+        .iter()
+        .map(|collider| density * collider.shape.get_area())
+        .collect();
+    let mass: f32 = masses.iter().sum();
+    if mass == 0. {
+        return (0., Vector2::zero(), 0.);
+    }
+    let center: Vector2 = colliders
+        .iter()
+        .zip(&masses)
+        .fold(Vector2::zero(), |sum, (collider, &m)| {
+            sum + collider.get_local_centroid() * m
+        })
+        / mass;
+
+    // parallel axis theorem, from the shape origin to its centroid and from there to the center of mass
+    let inertia: f32 = colliders
+        .iter()
+        .zip(&masses)
+        .map(|(collider, &m)| {
+            let shape_centroid: Vector2 = collider.shape.get_centroid();
+            let around_centroid: f32 =
+                collider.shape.get_moment_of_inertia(m) - m * shape_centroid.length_sqr();
+            around_centroid + m * (collider.get_local_centroid() - center).length_sqr()
+        })
+        .sum();
+    (mass, center, inertia)
+}
@@ -0,0 +1,226 @@
+use crate::rust_game_engine::math::Vector2;
+use crate::rust_game_engine::physics::contact::ContactPoint;
+use crate::rust_game_engine::physics::shape::Shape;
+use crate::rust_game_engine::physics::vector_math::*;
+
+/// Which feature the separating axis with the smallest overlap belongs to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AxisOwner {
+    /// a face normal of the first shape
+    First,
+    /// a face normal of the second shape
+    Second,
+    /// the direction between two core points, only used for round shapes
+    Points,
+}
+
+/// separating axis test, compound shapes overlap if any of their pieces do.
+/// Axis (pointing from a to b) and overlap of the deepest overlapping pieces, None if not colliding
+pub fn get_collision_axis_and_overlap(a: &Shape, b: &Shape) -> Option<(Vector2, f32)> {
+    get_piece_pairs(a, b)
+        .filter_map(|(a, b)| get_collision_info(a, b))
+        .map(|(u_axis, overlap, _)| (u_axis, overlap))
+        .max_by(|x, y| x.1.total_cmp(&y.1))
+}
+
+/// one manifold per pair of overlapping pieces: contact normal (pointing from a to b)
+/// and contact points, empty if not colliding
+pub fn get_contact_manifolds(a: &Shape, b: &Shape) -> Vec<(Vector2, Vec<ContactPoint>)> {
+    get_piece_pairs(a, b)
+        .filter_map(|(a, b)| get_contact_manifold(a, b))
+        .collect()
+}
+
+/// pairs of convex pieces whose bounding boxes overlap
+fn get_piece_pairs<'a>(a: &'a Shape, b: &'a Shape) -> impl Iterator<Item = (&'a Shape, &'a Shape)> {
+    a.get_pieces().iter().flat_map(move |a_piece| {
+        b.get_pieces()
+            .iter()
+            .filter(move |b_piece| {
+                a_piece
+                    .get_bounding_box()
+                    .check_collision_recs(&b_piece.get_bounding_box())
+            })
+            .map(move |b_piece| (a_piece, b_piece))
+    })
+}
+
+/// separating axis test of two convex shapes.
+/// Axis (pointing from a to b), overlap along it and the owner of the axis, None if not colliding
+pub fn get_collision_info(a: &Shape, b: &Shape) -> Option<(Vector2, f32, AxisOwner)> {
+    let (a_points, b_points): (Vec<Vector2>, Vec<Vector2>) =
+        (a.get_core_points(), b.get_core_points());
+    let (a_radius, b_radius): (f32, f32) = (a.get_radius(), b.get_radius());
+
+    let mut u_axes_to_be_checked: Vec<(Vector2, AxisOwner)> = Vec::new();
+    u_axes_to_be_checked.extend(
+        a.get_face_normals()
+            .into_iter()
+            .map(|u| (u, AxisOwner::First)),
+    );
+    u_axes_to_be_checked.extend(
+        b.get_face_normals()
+            .into_iter()
+            .map(|u| (u, AxisOwner::Second)),
+    );
+    // round shapes can also touch in the direction between two points, e.g. a circle and a corner
+    if a_radius > 0. || b_radius > 0. {
+        for &a_point in &a_points {
+            for &b_point in &b_points {
+                let dir: Vector2 = b_point - a_point;
+                if dir.length_sqr() > f32::EPSILON {
+                    u_axes_to_be_checked.push((dir.normalized(), AxisOwner::Points));
+                }
+            }
+        }
+    }
+
+    let mut smallest_overlap: f32 = f32::INFINITY;
+    let mut best_u_axis: Vector2 = Vector2::zero();
+    let mut best_owner: AxisOwner = AxisOwner::First;
+
+    let dir_a_b: Vector2 = get_center(&b_points) - get_center(&a_points);
+    for (mut u_axis, owner) in u_axes_to_be_checked {
+        if u_axis.dot(dir_a_b) < 0.0 {
+            u_axis.scale(-1.);
+        }
+        let (a_min, a_max) = project(&a_points, a_radius, u_axis);
+        let (b_min, b_max) = project(&b_points, b_radius, u_axis);
+
+        // check separating axis theorem
+        if a_max < b_min || b_max < a_min {
+            return None;
+        }
+
+        let overlap: f32 = f32::min(a_max, b_max) - f32::max(a_min, b_min);
+        if overlap < smallest_overlap {
+            smallest_overlap = overlap;
+            best_u_axis = u_axis;
+            best_owner = owner;
+        }
+    }
+    if smallest_overlap == f32::INFINITY {
+        // two circles at the same position
+        return Some((Vector2::new(0., 1.), a_radius + b_radius, AxisOwner::Points));
+    }
+    Some((best_u_axis, smallest_overlap, best_owner))
+}
+
+/// contact normal (pointing from a to b) and contact points of two convex shapes, None if not colliding.
+/// The points are found by clipping the incident edge against the reference edge
+pub fn get_contact_manifold(a: &Shape, b: &Shape) -> Option<(Vector2, Vec<ContactPoint>)> {
+    let (u_axis, overlap, owner) = get_collision_info(a, b)?;
+
+    // the reference shape owns the separating axis, its normal points to the incident one
+    let points: Vec<ContactPoint> = match owner {
+        AxisOwner::First => clip_incident_edge(a, b, u_axis),
+        AxisOwner::Second => clip_incident_edge(b, a, -u_axis),
+        AxisOwner::Points => vec![],
+    };
+    if !points.is_empty() {
+        return Some((u_axis, points));
+    }
+
+    // a single point between the closest features
+    let (_, b_point) = get_closest_core_points(a, b);
+    let point: ContactPoint = ContactPoint::new(b_point - u_axis * b.get_radius(), overlap);
+    Some((u_axis, vec![point]))
+}
+
+fn clip_incident_edge(reference: &Shape, incident: &Shape, u_normal: Vector2) -> Vec<ContactPoint> {
+    let (ref_start, ref_end) = reference.get_best_edge(u_normal);
+    let (inc_start, inc_end) = incident.get_best_edge(-u_normal);
+    let incident_radius: f32 = incident.get_radius();
+
+    // clip the incident edge to the side planes of the reference edge
+    let u_tangent: Vector2 = (ref_end - ref_start).normalized();
+    let mut clipped: Vec<Vector2> = if inc_start == inc_end {
+        vec![inc_start]
+    } else {
+        vec![inc_start, inc_end]
+    };
+    clipped = clip_segment(&clipped, -u_tangent, -u_tangent.dot(ref_start));
+    clipped = clip_segment(&clipped, u_tangent, u_tangent.dot(ref_end));
+
+    // keep the points that lie behind the reference face
+    let face_offset: f32 = u_normal.dot(ref_start) + reference.get_radius();
+    clipped
+        .into_iter()
+        .filter_map(|p| {
+            let surface_point: Vector2 = p - u_normal * incident_radius;
+            let depth: f32 = face_offset - u_normal.dot(surface_point);
+            (depth >= 0.).then(|| ContactPoint::new(surface_point, depth))
+        })
+        .collect()
+}
+
+/// keeps the part of the segment (or point) where `u_dir.dot(p) <= offset`
+fn clip_segment(segment: &[Vector2], u_dir: Vector2, offset: f32) -> Vec<Vector2> {
+    if segment.len() < 2 {
+        return segment
+            .iter()
+            .copied()
+            .filter(|&p| u_dir.dot(p) <= offset)
+            .collect();
+    }
+    let (p1, p2): (Vector2, Vector2) = (segment[0], segment[1]);
+    let d1: f32 = u_dir.dot(p1) - offset;
+    let d2: f32 = u_dir.dot(p2) - offset;
+
+    let mut result: Vec<Vector2> = Vec::new();
+    if d1 <= 0. {
+        result.push(p1);
+    }
+    if d2 <= 0. {
+        result.push(p2);
+    }
+    if d1 * d2 < 0. {
+        let t: f32 = d1 / (d1 - d2);
+        result.push(p1 + (p2 - p1) * t);
+    }
+    result
+}
+
+/// closest points between the core edges of both shapes, the first one is on a
+fn get_closest_core_points(a: &Shape, b: &Shape) -> (Vector2, Vector2) {
+    let a_edges: Vec<(Vector2, Vector2)> = get_core_edges(a);
+    let b_edges: Vec<(Vector2, Vector2)> = get_core_edges(b);
+
+    let mut best: (Vector2, Vector2) = (a_edges[0].0, b_edges[0].0);
+    let mut best_distance: f32 = f32::INFINITY;
+    for &(a1, a2) in &a_edges {
+        for &(b1, b2) in &b_edges {
+            let (p, q) = closest_points_on_segments(a1, a2, b1, b2);
+            let distance: f32 = (q - p).length_sqr();
+            if distance < best_distance {
+                best_distance = distance;
+                best = (p, q);
+            }
+        }
+    }
+    best
+}
+
+fn get_core_edges(shape: &Shape) -> Vec<(Vector2, Vector2)> {
+    let points: Vec<Vector2> = shape.get_core_points();
+    match points.len() {
+        1 => vec![(points[0], points[0])],
+        2 => vec![(points[0], points[1])],
+        n => (0..n).map(|i| (points[i], points[(i + 1) % n])).collect(),
+    }
+}
+
+/// interval of the shape projected onto `u_axis`
+pub fn project(points: &[Vector2], radius: f32, u_axis: Vector2) -> (f32, f32) {
+    let (min, max) = points
+        .iter()
+        .map(|p| u_axis.dot(*p))
+        .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), value| {
+            (min.min(value), max.max(value))
+        });
+    (min - radius, max + radius)
+}
+
+fn get_center(points: &[Vector2]) -> Vector2 {
+    points.iter().fold(Vector2::zero(), |sum, &p| sum + p) / points.len() as f32
+}
@@ -0,0 +1,81 @@
+/// Decides which object pairs are tested in the narrow phase
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CollisionFilter {
+    /// bits of the categories the object belongs to
+    pub category: u32,
+    /// bits of the categories the object collides with
+    pub mask: u32,
+    /// 0: no group. Objects in the same positive group always collide,
+    /// objects in the same negative group never collide, regardless of category and mask
+    pub group: i32,
+}
+
+impl CollisionFilter {
+    /// collides with everything
+    pub const DEFAULT: CollisionFilter = CollisionFilter::new(1, u32::MAX);
+    /// collides with nothing
+    pub const NONE: CollisionFilter = CollisionFilter::new(1, 0);
+
+    pub const fn new(category: u32, mask: u32) -> CollisionFilter {
+        CollisionFilter {
+            category,
+            mask,
+            group: 0,
+        }
+    }
+
+    pub const fn with_group(mut self, group: i32) -> CollisionFilter {
+        self.group = group;
+        self
+    }
+
+    pub fn should_collide(&self, other: &CollisionFilter) -> bool {
+        if self.group != 0 && self.group == other.group {
+            return self.group > 0;
+        }
+        self.mask & other.category != 0 && other.mask & self.category != 0
+    }
+}
+
+impl Default for CollisionFilter {
+    fn default() -> Self {
+        CollisionFilter::DEFAULT
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAYER: u32 = 1 << 0;
+    const ENEMY: u32 = 1 << 1;
+    const BULLET: u32 = 1 << 2;
+
+    #[test]
+    fn category_has_to_be_in_both_masks() {
+        let player: CollisionFilter = CollisionFilter::new(PLAYER, ENEMY | BULLET);
+        let enemy: CollisionFilter = CollisionFilter::new(ENEMY, PLAYER);
+        let bullet: CollisionFilter = CollisionFilter::new(BULLET, PLAYER | ENEMY);
+
+        assert!(player.should_collide(&enemy) && enemy.should_collide(&player));
+        assert!(player.should_collide(&bullet) && bullet.should_collide(&player));
+        // the bullet wants to hit the enemy, but the enemy mask doesn't contain bullets
+        assert!(!bullet.should_collide(&enemy) && !enemy.should_collide(&bullet));
+        assert!(!player.should_collide(&player));
+        assert!(!CollisionFilter::NONE.should_collide(&CollisionFilter::DEFAULT));
+    }
+
+    #[test]
+    fn group_overrides_category_and_mask() {
+        let ragdoll_part: CollisionFilter = CollisionFilter::DEFAULT.with_group(-1);
+        let other_ragdoll_part: CollisionFilter = CollisionFilter::DEFAULT.with_group(-2);
+        assert!(!ragdoll_part.should_collide(&ragdoll_part));
+        assert!(ragdoll_part.should_collide(&other_ragdoll_part));
+        assert!(ragdoll_part.should_collide(&CollisionFilter::DEFAULT));
+
+        let team: CollisionFilter = CollisionFilter::NONE.with_group(3);
+        assert!(team.should_collide(&team));
+        assert!(!team.should_collide(&CollisionFilter::NONE.with_group(4)));
+        assert!(!team.should_collide(&CollisionFilter::DEFAULT));
+    }
+}
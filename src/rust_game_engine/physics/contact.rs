@@ -0,0 +1,200 @@
+use crate::rust_game_engine::constants::*;
+use crate::rust_game_engine::math::Vector2;
+use crate::rust_game_engine::object_store::ObjectId;
+use crate::rust_game_engine::physics::game_object::PhysicsObject;
+use crate::rust_game_engine::physics::material::MaterialMixing;
+use crate::rust_game_engine::physics::vector_math::*;
+
+pub struct ContactPoint {
+    pub pos: Vector2,
+    pub depth: f32,
+
+    // solver data, set up in `Contact::pre_step`
+    pub r1: Vector2,
+    pub r2: Vector2,
+    pub normal_mass: f32,
+    pub tangent_mass: f32,
+    pub velocity_bias: f32,
+    /// accumulated over all solver iterations of one step
+    pub normal_impulse: f32,
+    pub tangent_impulse: f32,
+}
+
+/// Contact manifold between two colliding objects
+pub struct Contact {
+    pub i: ObjectId,
+    pub j: ObjectId,
+    /// indices of the touching colliders of object i and object j
+    pub collider_i: usize,
+    pub collider_j: usize,
+    /// unit normal pointing from object i to object j
+    pub normal: Vector2,
+    pub points: Vec<ContactPoint>,
+    /// material values combined from both objects
+    pub restitution: f32,
+    pub static_friction: f32,
+    pub dynamic_friction: f32,
+
+    /// object positions when the contact was found, to track the depth during position iterations
+    pub start_pos_i: Vector2,
+    pub start_pos_j: Vector2,
+}
+
+impl ContactPoint {
+    pub fn new(pos: Vector2, depth: f32) -> ContactPoint {
+        ContactPoint {
+            pos,
+            depth,
+            r1: Vector2::zero(),
+            r2: Vector2::zero(),
+            normal_mass: 0.,
+            tangent_mass: 0.,
+            velocity_bias: 0.,
+            normal_impulse: 0.,
+            tangent_impulse: 0.,
+        }
+    }
+}
+
+impl Contact {
+    pub fn new(
+        i: ObjectId,
+        j: ObjectId,
+        a: &PhysicsObject,
+        b: &PhysicsObject,
+        mixing: &MaterialMixing,
+        normal: Vector2,
+        points: Vec<ContactPoint>,
+    ) -> Contact {
+        let (restitution, static_friction, dynamic_friction) = mixing.mix(&a.material, &b.material);
+        Contact {
+            i,
+            j,
+            collider_i: 0,
+            collider_j: 0,
+            normal,
+            points,
+            restitution,
+            static_friction,
+            dynamic_friction,
+            start_pos_i: a.obj.pos,
+            start_pos_j: b.obj.pos,
+        }
+    }
+
+    /// unit tangent, perpendicular to the normal
+    pub fn get_tangent(&self) -> Vector2 {
+        Vector2::new(-self.normal.y, self.normal.x)
+    }
+
+    pub fn get_max_depth(&self) -> f32 {
+        self.points.iter().map(|p| p.depth).fold(0., f32::max)
+    }
+
+    /// total normal impulse of all points, accumulated over the velocity iterations
+    pub fn get_normal_impulse(&self) -> f32 {
+        self.points.iter().map(|p| p.normal_impulse).sum()
+    }
+
+    /// precomputes everything that stays the same during the velocity iterations
+    pub fn pre_step(&mut self, a: &PhysicsObject, b: &PhysicsObject) {
+        let (_, m1_inv): (f32, f32) = a.physics.get_masses();
+        let (_, m2_inv): (f32, f32) = b.physics.get_masses();
+        let (_, i1_inv): (f32, f32) = a.physics.get_inertias();
+        let (_, i2_inv): (f32, f32) = b.physics.get_inertias();
+
+        let u_tangent: Vector2 = self.get_tangent();
+        let get_effective_mass = |r1: Vector2, r2: Vector2, u_dir: Vector2| -> f32 {
+            let r1d: f32 = cross(r1, u_dir);
+            let r2d: f32 = cross(r2, u_dir);
+            let k: f32 = m1_inv + m2_inv + r1d * r1d * i1_inv + r2d * r2d * i2_inv;
+            if k > 0. {
+                1. / k
+            } else {
+                0.
+            }
+        };
+
+        for point in &mut self.points {
+            point.r1 = point.pos - a.obj.pos;
+            point.r2 = point.pos - b.obj.pos;
+
+            point.normal_mass = get_effective_mass(point.r1, point.r2, self.normal);
+            point.tangent_mass = get_effective_mass(point.r1, point.r2, u_tangent);
+
+            // restitution only for real impacts, resting contacts should not bounce
+            let relative_vel: Vector2 =
+                b.physics.get_point_vel(point.r2) - a.physics.get_point_vel(point.r1);
+            let vn: f32 = relative_vel.dot(self.normal);
+            point.velocity_bias = if vn < -RESTITUTION_VELOCITY_THRESHOLD {
+                -self.restitution * vn
+            } else {
+                0.
+            };
+            point.normal_impulse = 0.;
+            point.tangent_impulse = 0.;
+        }
+    }
+
+    /// one iteration of sequential impulses
+    pub fn solve_velocity(&mut self, a: &mut PhysicsObject, b: &mut PhysicsObject) {
+        let u_tangent: Vector2 = self.get_tangent();
+
+        for point in &mut self.points {
+            let relative_vel: Vector2 =
+                b.physics.get_point_vel(point.r2) - a.physics.get_point_vel(point.r1);
+            let vn: f32 = relative_vel.dot(self.normal);
+
+            let lambda: f32 = -point.normal_mass * (vn - point.velocity_bias);
+
+            // clamp the accumulated impulse, contacts can only push
+            let new_impulse: f32 = (point.normal_impulse + lambda).max(0.);
+            let applied: f32 = new_impulse - point.normal_impulse;
+            point.normal_impulse = new_impulse;
+
+            let impulse: Vector2 = self.normal * applied;
+            a.physics.add_impulse(-impulse, point.r1);
+            b.physics.add_impulse(impulse, point.r2);
+
+            // coulomb friction, bounded by the normal impulse
+            let relative_vel: Vector2 =
+                b.physics.get_point_vel(point.r2) - a.physics.get_point_vel(point.r1);
+            let vt: f32 = relative_vel.dot(u_tangent);
+            let lambda: f32 = -point.tangent_mass * vt;
+
+            let mut new_impulse: f32 = point.tangent_impulse + lambda;
+            if new_impulse.abs() > self.static_friction * point.normal_impulse {
+                // sliding
+                let max_friction: f32 = self.dynamic_friction * point.normal_impulse;
+                new_impulse = new_impulse.clamp(-max_friction, max_friction);
+            }
+            let applied: f32 = new_impulse - point.tangent_impulse;
+            point.tangent_impulse = new_impulse;
+
+            let impulse: Vector2 = u_tangent * applied;
+            a.physics.add_impulse(-impulse, point.r1);
+            b.physics.add_impulse(impulse, point.r2);
+        }
+    }
+
+    /// pushes the objects apart, split by their inverse masses
+    pub fn correct_positions(&self, a: &mut PhysicsObject, b: &mut PhysicsObject) {
+        let (_, m1_inv): (f32, f32) = a.physics.get_masses();
+        let (_, m2_inv): (f32, f32) = b.physics.get_masses();
+        if m1_inv + m2_inv == 0. {
+            return;
+        }
+
+        // how much the objects have already been separated since the contact was found
+        let separated: f32 =
+            ((b.obj.pos - self.start_pos_j) - (a.obj.pos - self.start_pos_i)).dot(self.normal);
+        let depth: f32 = self.get_max_depth() - separated;
+
+        let correction: f32 = (depth - PENETRATION_SLOP).max(0.)
+            * POSITION_CORRECTION_PERCENT
+            / (m1_inv + m2_inv);
+
+        a.correct_position(&(-self.normal * correction * m1_inv));
+        b.correct_position(&(self.normal * correction * m2_inv));
+    }
+}
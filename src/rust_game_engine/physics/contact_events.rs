@@ -0,0 +1,202 @@
+use crate::rust_game_engine::engine_core::Scene;
+use crate::rust_game_engine::math::Vector2;
+use crate::rust_game_engine::object_store::ObjectId;
+use crate::rust_game_engine::physics::contact::Contact;
+use std::collections::BTreeMap;
+
+/// Called once per event after the physics steps of a frame
+pub type ContactCallback = Box<dyn FnMut(&mut Scene, &ContactEvent)>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ContactEventKind {
+    /// the colliders started touching in this step
+    Begin,
+    /// the colliders were already touching in the step before
+    Persist,
+    /// the colliders stopped touching, normal is the last known one, depth and impulse are 0
+    End,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ContactEvent {
+    pub kind: ContactEventKind,
+    pub a: ObjectId,
+    pub b: ObjectId,
+    /// indices into the `colliders` of a and b, which sub shapes touched
+    pub collider_a: usize,
+    pub collider_b: usize,
+    /// unit normal pointing from a to b
+    pub normal: Vector2,
+    /// deepest penetration during the step
+    pub depth: f32,
+    /// normal impulse applied by the solver during the step
+    pub impulse: f32,
+}
+
+/// Remembers which collider pairs touched in the last step to tell begin, persist and end apart
+#[derive(Default)]
+pub struct ContactTracker {
+    touching: BTreeMap<ColliderPair, ContactEvent>,
+    current: BTreeMap<ColliderPair, ContactEvent>,
+}
+
+/// (object a, collider of a), (object b, collider of b)
+type ColliderPair = ((ObjectId, usize), (ObjectId, usize));
+
+impl ContactEvent {
+    pub fn involves(&self, id: ObjectId) -> bool {
+        self.a == id || self.b == id
+    }
+
+    /// the object `id` touched, `None` if `id` is not part of this contact
+    pub fn get_other(&self, id: ObjectId) -> Option<ObjectId> {
+        if self.a == id {
+            Some(self.b)
+        } else if self.b == id {
+            Some(self.a)
+        } else {
+            None
+        }
+    }
+}
+
+impl ContactTracker {
+    pub fn new() -> ContactTracker {
+        ContactTracker::default()
+    }
+
+    /// resolved contacts of one sub step, a pair touching in any sub step counts as touching
+    pub fn add_contacts(&mut self, contacts: &[Contact]) {
+        for contact in contacts {
+            let depth: f32 = contact.get_max_depth();
+            let impulse: f32 = contact.get_normal_impulse();
+
+            self.current
+                .entry((
+                    (contact.i, contact.collider_i),
+                    (contact.j, contact.collider_j),
+                ))
+                .and_modify(|event| {
+                    event.normal = contact.normal;
+                    event.depth = event.depth.max(depth);
+                    event.impulse += impulse;
+                })
+                .or_insert(ContactEvent {
+                    kind: ContactEventKind::Begin,
+                    a: contact.i,
+                    b: contact.j,
+                    collider_a: contact.collider_i,
+                    collider_b: contact.collider_j,
+                    normal: contact.normal,
+                    depth,
+                    impulse,
+                });
+        }
+    }
+
+    /// keeps the pairs of the last step whose objects both rest, the narrow phase skips them
+    pub fn keep_resting_contacts(&mut self, is_resting: impl Fn(ObjectId) -> bool) {
+        for (&pair, event) in &self.touching {
+            let ((a, _), (b, _)) = pair;
+            if is_resting(a) && is_resting(b) {
+                self.current.entry(pair).or_insert(ContactEvent {
+                    impulse: 0.,
+                    ..*event
+                });
+            }
+        }
+    }
+
+    /// object pairs touching in the last finished step
+    pub fn get_touching_pairs(&self) -> Vec<(ObjectId, ObjectId)> {
+        let mut pairs: Vec<(ObjectId, ObjectId)> = self
+            .touching
+            .keys()
+            .map(|&((a, _), (b, _))| (a, b))
+            .collect();
+        // the keys are sorted by collider, the pairs of different colliders of the same objects
+        // are not always next to each other
+        pairs.sort_unstable();
+        pairs.dedup();
+        pairs
+    }
+
+    /// compares the collider pairs of this step with the last one, ordered by pair
+    pub fn finish_step(&mut self) -> Vec<ContactEvent> {
+        let mut events: Vec<ContactEvent> = Vec::new();
+
+        for (pair, event) in &mut self.current {
+            if self.touching.contains_key(pair) {
+                event.kind = ContactEventKind::Persist;
+            }
+            events.push(*event);
+        }
+        for (pair, event) in &self.touching {
+            if !self.current.contains_key(pair) {
+                events.push(ContactEvent {
+                    kind: ContactEventKind::End,
+                    depth: 0.,
+                    impulse: 0.,
+                    ..*event
+                });
+            }
+        }
+
+        self.touching = std::mem::take(&mut self.current);
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rust_game_engine::physics::game_object::PhysicsObject;
+    use crate::rust_game_engine::physics::material::Material;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn dropped_ball_begins_persists_and_ends_with_one_callback_per_event() {
+        let mut scene: Scene = Scene::new_headless(1. / 60.);
+        let mut ground: PhysicsObject = PhysicsObject::generate_walls().remove(1);
+        ground.material = Material::METAL;
+        let ground: ObjectId = scene.add_game_object(ground);
+        let ball: PhysicsObject = PhysicsObject::new_circle(
+            Vector2::new(500., 600.),
+            20.,
+            Material::METAL,
+            "ball".to_string(),
+        );
+        let ball: ObjectId = scene.add_game_object(ball);
+
+        let called: Rc<RefCell<Vec<ContactEventKind>>> = Rc::default();
+        let called_in_callback: Rc<RefCell<Vec<ContactEventKind>>> = Rc::clone(&called);
+        scene.on_contact(Box::new(move |_, event| {
+            called_in_callback.borrow_mut().push(event.kind);
+        }));
+
+        let mut kinds: Vec<ContactEventKind> = vec![];
+        for frame in 0..240 {
+            if frame == 180 {
+                // thrown up again after resting on the ground
+                let obj: &mut PhysicsObject = scene.get_game_object_mut(ball).unwrap();
+                *obj.physics.get_vel_mut().unwrap() = Vector2::new(0., -300.);
+            }
+            scene.frame_logic(1. / 60.);
+            for event in &scene.contact_events {
+                assert_eq!((event.a, event.b), (ground, ball));
+                kinds.push(event.kind);
+            }
+        }
+
+        assert_eq!(kinds.first(), Some(&ContactEventKind::Begin));
+        assert!(kinds.contains(&ContactEventKind::Persist));
+        assert_eq!(kinds.last(), Some(&ContactEventKind::End));
+        let mut touching: bool = false;
+        for &kind in &kinds {
+            assert_eq!(kind == ContactEventKind::Begin, !touching, "{:?}", kinds);
+            touching = kind != ContactEventKind::End;
+        }
+        assert_eq!(*called.borrow(), kinds);
+    }
+}
@@ -0,0 +1,331 @@
+use crate::rust_game_engine::math::Vector2;
+use crate::rust_game_engine::physics::physics_addition::Polygon;
+use crate::rust_game_engine::physics::vector_math::*;
+use std::fmt;
+
+/// tolerance for corners that lie on a line, relative to the squared size of the outline
+/// so that tiny and huge outlines are treated the same
+const COLLINEAR_EPSILON: f32 = 1e-6;
+
+/// Why an outline can't be turned into a polygon
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PolygonError {
+    /// less than 3 distinct corners, or all corners on one line
+    TooFewPoints,
+    /// two edges cross or touch each other
+    SelfIntersecting,
+    /// the corners have to be ordered like the ones of `Polygon::new_regular_polygon`,
+    /// clockwise on screen
+    WrongWinding,
+    /// a single polygon has to be convex, concave outlines are split by `Shape::new_concave`
+    NotConvex,
+}
+
+impl fmt::Display for PolygonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message: &str = match self {
+            PolygonError::TooFewPoints => {
+                "a polygon needs at least 3 corners that are not on one line"
+            }
+            PolygonError::SelfIntersecting => "the edges of the polygon intersect each other",
+            PolygonError::WrongWinding => "the corners of the polygon have to be ordered clockwise",
+            PolygonError::NotConvex => "the polygon is concave, use a concave shape instead",
+        };
+        f.write_str(message)
+    }
+}
+
+impl std::error::Error for PolygonError {}
+
+/// checks the outline and splits it into as few convex pieces as the greedy merging finds,
+/// a convex outline is returned as a single piece
+pub fn decompose(corners: &[Vector2]) -> Result<Vec<Vec<Vector2>>, PolygonError> {
+    let epsilon: f32 = get_epsilon(corners);
+    let corners: Vec<Vector2> = remove_duplicates(corners, epsilon);
+    validate_outline(&corners)?;
+
+    let triangles: Vec<Vec<usize>> =
+        triangulate(&corners, epsilon).ok_or(PolygonError::SelfIntersecting)?;
+    let pieces: Vec<Vec<usize>> = merge_convex(&corners, triangles, epsilon);
+
+    Ok(pieces
+        .into_iter()
+        .map(|piece| {
+            let piece: Vec<Vector2> = piece.into_iter().map(|i| corners[i]).collect();
+            remove_collinear(&piece, epsilon)
+        })
+        .collect())
+}
+
+/// `Ok` if the corners describe a simple polygon with the winding of `Polygon::from_corners`
+pub fn validate_outline(corners: &[Vector2]) -> Result<(), PolygonError> {
+    let n: usize = corners.len();
+    if n < 3 {
+        return Err(PolygonError::TooFewPoints);
+    }
+    let epsilon: f32 = get_epsilon(corners);
+    let is_on_one_line: bool = corners
+        .iter()
+        .all(|&c| cross(corners[1] - corners[0], c - corners[0]).abs() <= epsilon);
+    if is_on_one_line {
+        return Err(PolygonError::TooFewPoints);
+    }
+
+    for i in 0..n {
+        let (a1, a2): (Vector2, Vector2) = (corners[i], corners[(i + 1) % n]);
+        // neighbouring edges only share a corner, unless they fold back onto each other
+        let a3: Vector2 = corners[(i + 2) % n];
+        let turn: f32 = cross(a2 - a1, a3 - a2);
+        if turn.abs() <= epsilon && (a2 - a1).dot(a3 - a2) < 0. {
+            return Err(PolygonError::SelfIntersecting);
+        }
+
+        for j in (i + 2)..n {
+            if (j + 1) % n == i {
+                continue;
+            }
+            let (b1, b2): (Vector2, Vector2) = (corners[j], corners[(j + 1) % n]);
+            let (p, q) = closest_points_on_segments(a1, a2, b1, b2);
+            if (q - p).length_sqr() <= epsilon {
+                return Err(PolygonError::SelfIntersecting);
+            }
+        }
+    }
+
+    if Polygon::get_signed_area_of(corners) < 0. {
+        return Err(PolygonError::WrongWinding);
+    }
+    Ok(())
+}
+
+/// like `validate_outline`, but concave outlines are rejected too
+pub fn validate_convex_outline(corners: &[Vector2]) -> Result<(), PolygonError> {
+    validate_outline(corners)?;
+    let indices: Vec<usize> = (0..corners.len()).collect();
+    if !is_convex(corners, &indices, get_epsilon(corners)) {
+        return Err(PolygonError::NotConvex);
+    }
+    Ok(())
+}
+
+/// area weighted center of a simple polygon
+pub fn get_centroid(corners: &[Vector2]) -> Vector2 {
+    let mut sum: Vector2 = Vector2::zero();
+    let mut area_sum: f32 = 0.;
+    for i in 0..corners.len() {
+        let (a, b): (Vector2, Vector2) = (corners[i], corners[(i + 1) % corners.len()]);
+        let c: f32 = cross(a, b);
+        sum += (a + b) * c;
+        area_sum += c;
+    }
+    if area_sum == 0. {
+        return corners.iter().fold(Vector2::zero(), |sum, &c| sum + c) / corners.len() as f32;
+    }
+    sum / (3. * area_sum)
+}
+
+/// ear clipping, indices of the triangles. `None` if no ear was found, which only
+/// happens for outlines that are not simple
+fn triangulate(corners: &[Vector2], epsilon: f32) -> Option<Vec<Vec<usize>>> {
+    let mut remaining: Vec<usize> = (0..corners.len()).collect();
+    let mut triangles: Vec<Vec<usize>> = Vec::new();
+
+    while remaining.len() > 3 {
+        let n: usize = remaining.len();
+        let ear: usize = (0..n).find(|&k| {
+            let (prev, curr, next): (usize, usize, usize) = (
+                remaining[(k + n - 1) % n],
+                remaining[k],
+                remaining[(k + 1) % n],
+            );
+            let (a, b, c): (Vector2, Vector2, Vector2) =
+                (corners[prev], corners[curr], corners[next]);
+            let turn: f32 = cross(b - a, c - b);
+            if turn.abs() <= epsilon {
+                // corner on a line, can be dropped without adding a triangle
+                return true;
+            }
+            turn > 0.
+                && remaining
+                    .iter()
+                    .filter(|&&i| i != prev && i != curr && i != next)
+                    .all(|&i| !is_in_triangle(corners[i], a, b, c))
+        })?;
+
+        let (prev, curr, next): (usize, usize, usize) = (
+            remaining[(ear + n - 1) % n],
+            remaining[ear],
+            remaining[(ear + 1) % n],
+        );
+        if cross(corners[curr] - corners[prev], corners[next] - corners[curr]) > epsilon {
+            triangles.push(vec![prev, curr, next]);
+        }
+        remaining.remove(ear);
+    }
+    triangles.push(remaining);
+    Some(triangles)
+}
+
+/// Hertel-Mehlhorn: removes diagonals between pieces as long as the merged piece stays convex
+fn merge_convex(corners: &[Vector2], mut pieces: Vec<Vec<usize>>, epsilon: f32) -> Vec<Vec<usize>> {
+    'merging: loop {
+        for a in 0..pieces.len() {
+            for b in (a + 1)..pieces.len() {
+                let Some(merged) = merge_pieces(&pieces[a], &pieces[b]) else {
+                    continue;
+                };
+                if is_convex(corners, &merged, epsilon) {
+                    pieces[a] = merged;
+                    pieces.remove(b);
+                    continue 'merging;
+                }
+            }
+        }
+        return pieces;
+    }
+}
+
+/// both pieces joined along their shared edge, `None` if they don't share one
+fn merge_pieces(a: &[usize], b: &[usize]) -> Option<Vec<usize>> {
+    let (na, nb): (usize, usize) = (a.len(), b.len());
+    for ia in 0..na {
+        let (p, q): (usize, usize) = (a[ia], a[(ia + 1) % na]);
+        // the shared edge goes the other way round in b
+        let Some(ib) = (0..nb).find(|&ib| b[ib] == q && b[(ib + 1) % nb] == p) else {
+            continue;
+        };
+        // all of a starting at q and ending at p, then the rest of b
+        let mut merged: Vec<usize> = (1..=na).map(|k| a[(ia + k) % na]).collect();
+        merged.extend((2..nb).map(|k| b[(ib + k) % nb]));
+        return Some(merged);
+    }
+    None
+}
+
+fn is_convex(corners: &[Vector2], piece: &[usize], epsilon: f32) -> bool {
+    let n: usize = piece.len();
+    (0..n).all(|k| {
+        let (a, b, c): (Vector2, Vector2, Vector2) = (
+            corners[piece[k]],
+            corners[piece[(k + 1) % n]],
+            corners[piece[(k + 2) % n]],
+        );
+        cross(b - a, c - b) >= -epsilon
+    })
+}
+
+fn is_in_triangle(p: Vector2, a: Vector2, b: Vector2, c: Vector2) -> bool {
+    cross(b - a, p - a) >= 0. && cross(c - b, p - b) >= 0. && cross(a - c, p - c) >= 0.
+}
+
+/// `COLLINEAR_EPSILON` scaled by the squared size of the outline
+fn get_epsilon(corners: &[Vector2]) -> f32 {
+    let (mut min, mut max): (Vector2, Vector2) = (
+        Vector2::new(f32::INFINITY, f32::INFINITY),
+        Vector2::new(f32::NEG_INFINITY, f32::NEG_INFINITY),
+    );
+    for &c in corners {
+        min = Vector2::new(min.x.min(c.x), min.y.min(c.y));
+        max = Vector2::new(max.x.max(c.x), max.y.max(c.y));
+    }
+    let size: f32 = (max.x - min.x).max(max.y - min.y).max(0.);
+    COLLINEAR_EPSILON * size * size
+}
+
+/// removes corners that are the same as the one before
+fn remove_duplicates(corners: &[Vector2], epsilon: f32) -> Vec<Vector2> {
+    let mut result: Vec<Vector2> = Vec::new();
+    for &corner in corners {
+        if result
+            .last()
+            .is_none_or(|&last| (corner - last).length_sqr() > epsilon)
+        {
+            result.push(corner);
+        }
+    }
+    while result.len() > 1
+        && (result[0] - result[result.len() - 1]).length_sqr() <= epsilon
+    {
+        result.pop();
+    }
+    result
+}
+
+/// removes corners in the middle of a straight edge
+fn remove_collinear(corners: &[Vector2], epsilon: f32) -> Vec<Vector2> {
+    let n: usize = corners.len();
+    (0..n)
+        .filter(|&k| {
+            let (a, b, c): (Vector2, Vector2, Vector2) =
+                (corners[(k + n - 1) % n], corners[k], corners[(k + 1) % n]);
+            cross(b - a, c - b).abs() > epsilon
+        })
+        .map(|k| corners[k])
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(x: f32, y: f32) -> Vector2 {
+        Vector2::new(x, y)
+    }
+
+    #[test]
+    fn outline_with_too_few_points() {
+        assert_eq!(
+            validate_outline(&[v(0., 0.), v(10., 0.)]),
+            Err(PolygonError::TooFewPoints)
+        );
+        let line: [Vector2; 3] = [v(0., 0.), v(10., 0.), v(20., 0.)];
+        assert_eq!(decompose(&line), Err(PolygonError::TooFewPoints));
+    }
+
+    #[test]
+    fn self_intersecting_outline() {
+        let bow_tie: [Vector2; 4] = [v(0., 0.), v(100., 100.), v(100., 0.), v(0., 100.)];
+        assert_eq!(decompose(&bow_tie), Err(PolygonError::SelfIntersecting));
+    }
+
+    #[test]
+    fn outline_with_wrong_winding() {
+        let square: [Vector2; 4] = [v(0., 0.), v(0., 100.), v(100., 100.), v(100., 0.)];
+        assert_eq!(decompose(&square), Err(PolygonError::WrongWinding));
+        let reversed: Vec<Vector2> = square.iter().rev().copied().collect();
+        assert!(decompose(&reversed).is_ok());
+    }
+
+    #[test]
+    fn tolerance_scales_with_the_outline() {
+        let tiny: [Vector2; 4] = [v(0., 0.), v(0.01, 0.), v(0.01, 0.01), v(0., 0.01)];
+        assert_eq!(validate_outline(&tiny), Ok(()));
+        let huge: [Vector2; 4] = [v(0., 0.), v(1e4, 0.), v(2e4, 0.01), v(0., 1e4)];
+        assert!(decompose(&huge).is_ok_and(|pieces| pieces.iter().all(|p| p.len() == 3)));
+    }
+
+    #[test]
+    fn l_shape_is_split_into_convex_pieces_with_the_same_area() {
+        let outline: [Vector2; 6] = [
+            v(0., 0.),
+            v(40., 0.),
+            v(40., 60.),
+            v(100., 60.),
+            v(100., 100.),
+            v(0., 100.),
+        ];
+        let pieces: Vec<Vec<Vector2>> = decompose(&outline).unwrap();
+        assert!(pieces.len() >= 2);
+        for piece in &pieces {
+            let indices: Vec<usize> = (0..piece.len()).collect();
+            assert!(is_convex(piece, &indices, 0.), "{:?} is concave", piece);
+        }
+        let area: f32 = pieces
+            .iter()
+            .map(|piece| Polygon::get_signed_area_of(piece))
+            .sum();
+        let expected: f32 = Polygon::get_signed_area_of(&outline);
+        assert!((expected - 6400.).abs() < 0.01);
+        assert!((area - expected).abs() < 0.01, "{} != {}", area, expected);
+    }
+}
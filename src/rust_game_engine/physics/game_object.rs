@@ -1,26 +1,134 @@
+use crate::rust_game_engine::backend::DrawCommand;
+use crate::rust_game_engine::color::Color;
 use crate::rust_game_engine::constants::*;
+use crate::rust_game_engine::math::{Rectangle, Vector2};
+use crate::rust_game_engine::physics::collider::{self, Collider};
+use crate::rust_game_engine::physics::collision;
+use crate::rust_game_engine::physics::collision_filter::CollisionFilter;
+use crate::rust_game_engine::physics::contact::ContactPoint;
+use crate::rust_game_engine::physics::decomposition::{self, PolygonError};
+use crate::rust_game_engine::physics::material::Material;
 use crate::rust_game_engine::physics::physics_addition::PhysicsAddition::*;
 use crate::rust_game_engine::physics::physics_addition::*;
+use crate::rust_game_engine::physics::shape::{self, Shape};
 use rand::prelude::*;
-use raylib::prelude::*;
 use std::collections::HashSet;
-use std::f32::consts::PI;
 
 pub struct PhysicsObject {
     pub obj: GameObject,
-    pub polygon: Polygon,
+    /// shapes of the object, contact events refer to them by index
+    pub colliders: Vec<Collider>,
     pub physics: PhysicsAddition,
+    pub material: Material,
+    /// sensors report overlaps as sensor events, but never collide
+    pub is_sensor: bool,
+    pub collision_filter: CollisionFilter,
+    /// continuous collision detection against static objects, for small and fast objects
+    pub ccd_enabled: bool,
 }
 
 pub struct GameObject {
     pub pos: Vector2,
     pub rotation: f32,
+    /// transform before the last physics step, used for render interpolation
+    pub prev_pos: Vector2,
+    pub prev_rotation: f32,
     pub color: Color,
-    pub name_tag: String,
+    /// does not have to be unique.
+    /// Private, so that objects in a scene are only renamed through `Scene::set_name_tag`
+    name_tag: String,
+    /// groups the object belongs to, e.g. "enemy", changed through `Scene::add_tag` and `Scene::remove_tag`
+    tags: Vec<String>,
+}
+
+impl GameObject {
+    pub fn get_name_tag(&self) -> &str {
+        &self.name_tag
+    }
+    pub fn get_tags(&self) -> &[String] {
+        &self.tags
+    }
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+
+    /// only for the scene, which also updates its `TagIndex`
+    pub(crate) fn set_name_tag(&mut self, name_tag: &str) {
+        self.name_tag = name_tag.to_string();
+    }
+    /// `false` if the object already had the tag
+    pub(crate) fn add_tag(&mut self, tag: &str) -> bool {
+        if self.has_tag(tag) {
+            return false;
+        }
+        self.tags.push(tag.to_string());
+        true
+    }
+    pub(crate) fn remove_tag(&mut self, tag: &str) {
+        self.tags.retain(|t| t != tag);
+    }
 }
 
 impl PhysicsObject {
-    pub fn new(pos: Vector2, mass: f32, name_tag: String) -> PhysicsObject {
+    /// random regular polygon, the mass is derived from the material density and the polygon area
+    pub fn new(pos: Vector2, radius: f32, material: Material, name_tag: String) -> PhysicsObject {
+        let corner_count: usize = rand::rng().random_range(3..=6);
+        let polygon: Polygon = Polygon::new_regular_polygon(pos, corner_count, radius);
+        Self::new_with_shape(pos, Shape::Polygon(polygon), material, name_tag)
+    }
+
+    pub fn new_circle(
+        pos: Vector2,
+        radius: f32,
+        material: Material,
+        name_tag: String,
+    ) -> PhysicsObject {
+        Self::new_with_shape(pos, Shape::new_circle(pos, radius), material, name_tag)
+    }
+
+    /// horizontal capsule, `half_length` is the distance from `pos` to the centers of the round ends
+    pub fn new_capsule(
+        pos: Vector2,
+        half_length: f32,
+        radius: f32,
+        material: Material,
+        name_tag: String,
+    ) -> PhysicsObject {
+        let shape: Shape = Shape::new_capsule(pos, half_length, radius);
+        Self::new_with_shape(pos, shape, material, name_tag)
+    }
+
+    /// any simple outline, concave ones are split into convex pieces. `corners` are in world space
+    /// and ordered clockwise on screen, the object is placed at their centroid
+    pub fn new_concave(
+        corners: &[Vector2],
+        material: Material,
+        name_tag: String,
+    ) -> Result<PhysicsObject, PolygonError> {
+        let pos: Vector2 = decomposition::get_centroid(corners);
+        let shape: Shape = Shape::new_concave(pos, corners)?;
+        Ok(Self::new_with_shape(pos, shape, material, name_tag))
+    }
+
+    /// dynamic object with a random color, the mass is derived from the material density and the shape area.
+    /// Polygon outlines are checked when the shape is built, see `Polygon::from_corners` and `Shape::new_concave`
+    pub fn new_with_shape(
+        pos: Vector2,
+        shape: Shape,
+        material: Material,
+        name_tag: String,
+    ) -> PhysicsObject {
+        Self::new_compound(pos, vec![Collider::new(shape)], material, name_tag)
+    }
+
+    /// dynamic object made of several shapes, e.g. a hammer from a capsule and a rectangle.
+    /// `pos` is the origin of the collider offsets, the object is placed at the combined center of mass
+    pub fn new_compound(
+        pos: Vector2,
+        mut colliders: Vec<Collider>,
+        material: Material,
+        name_tag: String,
+    ) -> PhysicsObject {
         let mut rng = rand::rng();
         let color: Color = Color::new(
             rng.random::<u8>(),
@@ -29,24 +137,30 @@ impl PhysicsObject {
             255,
         );
 
-        // A ~ pi*r*r => r ~ sqrt(A / pi)
-        let radius: f32 = (mass / PI).sqrt();
-        let polygon: Polygon = Polygon::new_regular_polygon(pos, rng.random_range(3..=6), radius);
+        let (mass, center, inertia) = collider::get_mass_properties(&colliders, material.density);
+        // the object rotates around its center of mass
+        let pos: Vector2 = pos + center;
+        for collider in &mut colliders {
+            collider.offset -= center;
+            collider.update_transform(pos, 0.);
+        }
 
         PhysicsObject {
             obj: GameObject {
                 pos,
                 color,
                 rotation: 0.,
+                prev_pos: pos,
+                prev_rotation: 0.,
                 name_tag,
+                tags: vec![],
             },
-            physics: Dynamic {
-                vel: Vector2::zero(),
-                accel: Vector2::new(0.0, GRAVITY),
-                mass,
-                inv_mass: 1. / mass,
-            },
-            polygon,
+            physics: PhysicsAddition::new_dynamic(mass, inertia, Vector2::zero()),
+            colliders,
+            material,
+            is_sensor: false,
+            collision_filter: CollisionFilter::default(),
+            ccd_enabled: false,
         }
     }
 
@@ -58,10 +172,17 @@ impl PhysicsObject {
                 pos,
                 color: Color::RED,
                 rotation: 0.,
+                prev_pos: pos,
+                prev_rotation: 0.,
                 name_tag: "ground_obj".to_string(),
+                tags: vec![],
             },
             physics: Static,
-            polygon,
+            colliders: vec![Collider::new(Shape::Polygon(polygon))],
+            material: Material::default(),
+            is_sensor: false,
+            collision_filter: CollisionFilter::default(),
+            ccd_enabled: false,
         }
     }
 
@@ -76,15 +197,23 @@ impl PhysicsObject {
         ];
         for (start, end) in wall_points {
             let polygon: Polygon = Polygon::new_polygon_line(start, end, 1.);
+            let pos: Vector2 = (start + end) / 2.;
             let obj: PhysicsObject = PhysicsObject {
                 obj: GameObject {
                     rotation: 0.,
                     color: Color::RED,
-                    pos: (start + end) / 2.,
+                    pos,
+                    prev_pos: pos,
+                    prev_rotation: 0.,
                     name_tag: "wall".to_string(),
+                    tags: vec![],
                 },
                 physics: Static,
-                polygon,
+                colliders: vec![Collider::new(Shape::Polygon(polygon))],
+                material: Material::default(),
+                is_sensor: false,
+                collision_filter: CollisionFilter::default(),
+                ccd_enabled: false,
             };
             result.push(obj);
         }
@@ -92,120 +221,159 @@ impl PhysicsObject {
         result
     }
 
-    pub fn resolve_collision_other(&mut self, other: &mut PhysicsObject) {
-        // return if none is dynamic
-        if matches!((&self.physics, &other.physics), (Static, Static)) {
-            return;
-        }
-        let Some((u_axis, overlap)) = self.get_collision_axis_and_overlap(other) else {
-            return;
-        };
-
-        let (m1, m1_inv): (f32, f32) = self.physics.get_masses();
-        let (m2, m2_inv): (f32, f32) = other.physics.get_masses();
-
-        let move_percentage_self: f32 = m1_inv / (m1_inv + m2_inv);
-        let move_percentage_other: f32 = m2_inv / (m1_inv + m2_inv);
-
-        let correction_self: Vector2 = -u_axis.scale_by(move_percentage_self * overlap);
-        let correction_other: Vector2 = u_axis.scale_by(move_percentage_other * overlap);
-
-        // separate objects
-        self.move_relative(&correction_self);
-        other.move_relative(&correction_other);
+    /// adds a tag before the object is put into a scene
+    pub fn with_tag(mut self, tag: &str) -> PhysicsObject {
+        self.obj.add_tag(tag);
+        self
+    }
 
-        // update velocity
-        match (self.physics.is_dynamic(), other.physics.is_dynamic()) {
-            (true, true) => {
-                let self_vel = self.physics.get_vel_mut().unwrap();
-                let other_vel = other.physics.get_vel_mut().unwrap();
+    /// turns the object into a sensor, e.g. `generate_ground(pos).into_sensor()` for a goal zone
+    pub fn into_sensor(mut self) -> PhysicsObject {
+        self.is_sensor = true;
+        self
+    }
 
-                let (v1n, v1t) = Self::unwrap_vec(self_vel, u_axis);
-                let (v2n, v2t) = Self::unwrap_vec(other_vel, u_axis);
+    /// 0: not affected by gravity, negative values make the object fall up
+    pub fn with_gravity_scale(mut self, scale: f32) -> PhysicsObject {
+        self.set_gravity_scale(scale);
+        self
+    }
+    /// also wakes the object up
+    pub fn set_gravity_scale(&mut self, scale: f32) {
+        self.physics.wake_up();
+        if let Dynamic {
+            ref mut gravity_scale,
+            ..
+        } = self.physics
+        {
+            *gravity_scale = scale;
+        }
+    }
 
-                // calculating new normal velocities (tangent remain same)
-                let v1n_new: f32 = (v1n * (m1 - m2) + 2. * m2 * v2n) / (m1 + m2) * BOUNCINESS;
-                let v2n_new: f32 = (v2n * (m2 - m1) + 2. * m1 * v1n) / (m1 + m2) * BOUNCINESS;
+    /// damping in 1/s, e.g. air drag or top down friction with the floor
+    pub fn with_damping(mut self, linear: f32, angular: f32) -> PhysicsObject {
+        self.set_damping(linear, angular);
+        self
+    }
+    /// also wakes the object up
+    pub fn set_damping(&mut self, linear: f32, angular: f32) {
+        self.physics.wake_up();
+        if let Dynamic {
+            ref mut linear_damping,
+            ref mut angular_damping,
+            ..
+        } = self.physics
+        {
+            *linear_damping = linear.max(0.);
+            *angular_damping = angular.max(0.);
+        }
+    }
 
-                *self_vel = Self::wrap_vec(v1n_new, v1t, u_axis);
-                *other_vel = Self::wrap_vec(v2n_new, v2t, u_axis);
-            }
-            (true, false) => {
-                let self_vel = self.physics.get_vel_mut().unwrap();
+    /// for bullets, see `ccd_enabled`
+    pub fn with_ccd(mut self) -> PhysicsObject {
+        self.ccd_enabled = true;
+        self
+    }
 
-                let (v1n, v1t) = Self::unwrap_vec(self_vel, u_axis);
+    /// keeps the shape, but the object is only moved by the velocity set by the user
+    pub fn into_kinematic(mut self) -> PhysicsObject {
+        self.physics = PhysicsAddition::new_kinematic(Vector2::zero(), 0.);
+        self
+    }
 
-                let v1n_new: f32 = -v1n * BOUNCINESS;
-                *self_vel = Self::wrap_vec(v1n_new, v1t, u_axis);
-            }
-            (false, true) => {
-                let other_vel = other.physics.get_vel_mut().unwrap();
+    pub fn with_collision_filter(mut self, collision_filter: CollisionFilter) -> PhysicsObject {
+        self.collision_filter = collision_filter;
+        self
+    }
 
-                let (v2n, v2t) = Self::unwrap_vec(other_vel, u_axis);
+    /// replaces the material, the mass and inertia of dynamic objects are recomputed from the new density.
+    /// Also wakes the object up
+    pub fn set_material(&mut self, material: Material) {
+        self.physics.wake_up();
+        self.material = material;
+        if let Dynamic {
+            ref mut mass,
+            ref mut inv_mass,
+            ref mut inertia,
+            ref mut inv_inertia,
+            ..
+        } = self.physics
+        {
+            (*mass, _, *inertia) = collider::get_mass_properties(&self.colliders, material.density);
+            *inv_mass = get_inverse(*mass);
+            *inv_inertia = get_inverse(*inertia);
+        }
+    }
 
-                let v2n_new: f32 = -v2n * BOUNCINESS;
-                *other_vel = Self::wrap_vec(v2n_new, v2t, u_axis);
-            }
-            (false, false) => {
-                unreachable!();
+    /// indices of the touching colliders of self and other, contact normal (pointing from self to other)
+    /// and contact points for every pair of touching convex pieces, empty if not colliding
+    pub fn get_contact_manifolds(
+        &self,
+        other: &PhysicsObject,
+    ) -> Vec<(usize, usize, Vector2, Vec<ContactPoint>)> {
+        let mut result: Vec<(usize, usize, Vector2, Vec<ContactPoint>)> = Vec::new();
+        for (self_index, other_index) in self.get_collider_pairs(other) {
+            let self_shape: &Shape = &self.colliders[self_index].shape;
+            let other_shape: &Shape = &other.colliders[other_index].shape;
+            for (normal, points) in collision::get_contact_manifolds(self_shape, other_shape) {
+                result.push((self_index, other_index, normal, points));
             }
         }
+        result
     }
 
+    /// axis and overlap of the deepest overlapping colliders
     pub fn get_collision_axis_and_overlap(&self, other: &PhysicsObject) -> Option<(Vector2, f32)> {
-        let mut u_axes_to_be_checked: Vec<Vector2> = Vec::new();
-        u_axes_to_be_checked.extend(self.get_all_u_axes());
-        u_axes_to_be_checked.extend(other.get_all_u_axes());
-
-        let mut smallest_overlap: f32 = f32::INFINITY;
-        let mut best_u_axis: Vector2 = Vector2::zero();
-
-        let dir_self_other: Vector2 = other.obj.pos - self.obj.pos;
-        for u_axis in &mut u_axes_to_be_checked {
-            if u_axis.dot(dir_self_other) < 0.0 {
-                u_axis.scale(-1.);
-            }
-            let mut self_min: f32 = f32::INFINITY;
-            let mut self_max: f32 = f32::NEG_INFINITY;
-            let mut other_min: f32 = f32::INFINITY;
-            let mut other_max: f32 = f32::NEG_INFINITY;
-
-            for c in &self.polygon.corners {
-                let value: f32 = u_axis.dot(*c);
-                self_min = self_min.min(value);
-                self_max = self_max.max(value);
-            }
-            for c in &other.polygon.corners {
-                let value: f32 = u_axis.dot(*c);
-                other_min = other_min.min(value);
-                other_max = other_max.max(value);
-            }
-            // check separating axis theorem
-            if self_max < other_min || other_max < self_min {
-                return None;
-            }
+        self.get_collider_pairs(other)
+            .into_iter()
+            .filter_map(|(self_index, other_index)| {
+                collision::get_collision_axis_and_overlap(
+                    &self.colliders[self_index].shape,
+                    &other.colliders[other_index].shape,
+                )
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+    }
 
-            let overlap: f32 = f32::min(self_max, other_max) - f32::max(self_min, other_min);
-            if overlap < smallest_overlap {
-                smallest_overlap = overlap;
-                best_u_axis = *u_axis;
+    /// index pairs of colliders whose bounding boxes overlap
+    fn get_collider_pairs(&self, other: &PhysicsObject) -> Vec<(usize, usize)> {
+        let mut result: Vec<(usize, usize)> = Vec::new();
+        for (self_index, self_collider) in self.colliders.iter().enumerate() {
+            let bb: Rectangle = self_collider.shape.get_bounding_box();
+            for (other_index, other_collider) in other.colliders.iter().enumerate() {
+                if bb.check_collision_recs(&other_collider.shape.get_bounding_box()) {
+                    result.push((self_index, other_index));
+                }
             }
         }
-        Some((best_u_axis, smallest_overlap))
-    }
-    pub fn get_all_u_axes(&self) -> Vec<Vector2> {
-        let mut result: Vec<Vector2> = Vec::new();
-        let corners: &Vec<Vector2> = &self.polygon.corners;
-        for i in 0..corners.len() {
-            let c1: Vector2 = corners[i];
-            let c2: Vector2 = corners[(i + 1) % corners.len()];
-            let normal: Vector2 = c2 - c1;
-            let u_tangent: Vector2 = Vector2::new(-normal.y, normal.x).normalized();
-            result.push(u_tangent);
-        }
         result
     }
 
+    /// convex pieces of all colliders
+    pub fn get_pieces(&self) -> impl Iterator<Item = &Shape> {
+        self.colliders
+            .iter()
+            .flat_map(|collider| collider.shape.get_pieces())
+    }
+
+    /// bounding box of all colliders
+    pub fn get_bounding_box(&self) -> Rectangle {
+        shape::combine_bounding_boxes(
+            self.colliders
+                .iter()
+                .map(|collider| collider.shape.get_bounding_box()),
+        )
+    }
+
+    /// `true` if the point (world space) is inside one of the colliders
+    pub fn contains_point(&self, point: Vector2) -> bool {
+        self.get_bounding_box().check_collision_point_rec(point)
+            && self
+                .colliders
+                .iter()
+                .any(|collider| collider.shape.contains_point(point))
+    }
+
     pub fn get_cell_positions(
         &self,
         (cell_count_x, cell_count_y): (usize, usize),
@@ -215,7 +383,7 @@ impl PhysicsObject {
             let y_new: usize = (y / HEIGHT_F * cell_count_y as f32) as usize;
             (x_new, y_new)
         };
-        let bb: Rectangle = self.polygon.bounding_box;
+        let bb: Rectangle = self.get_bounding_box();
         let (start_x_cell, start_y_cell) = to_cell_coords(bb.x, bb.y);
         let (end_x_cell, end_y_cell) = to_cell_coords(bb.x + bb.width, bb.y + bb.height);
 
@@ -228,46 +396,119 @@ impl PhysicsObject {
         result
     }
 
-    pub fn unwrap_vec(vel: &Vector2, u_axis: Vector2) -> (f32, f32) {
-        let u_tangent: Vector2 = Vector2::new(-u_axis.y, u_axis.x);
-        (vel.dot(u_axis), vel.dot(u_tangent))
-    }
-    pub fn wrap_vec(n: f32, t: f32, u_axis: Vector2) -> Vector2 {
-        let u_tangent: Vector2 = Vector2::new(-u_axis.y, u_axis.x);
-        let n_v: Vector2 = u_axis.scale_by(n);
-        let t_v: Vector2 = u_tangent.scale_by(t);
-        n_v + t_v
-    }
-
-    pub fn update_move(&mut self, delta_time: f32) {
+    pub fn update_move(&mut self, delta_time: f32, gravity: Vector2) {
         match self.physics {
             Dynamic {
-                accel, ref mut vel, ..
+                accel,
+                gravity_scale,
+                ref mut vel,
+                inv_mass,
+                force,
+                linear_damping,
+                ref mut angular_vel,
+                inv_inertia,
+                torque,
+                angular_damping,
+                sleeping,
+                ..
             } => {
-                let added_vel: Vector2 = accel * delta_time;
+                if sleeping {
+                    return;
+                }
+                let total_accel: Vector2 = accel + gravity * gravity_scale + force * inv_mass;
+                let added_vel: Vector2 = total_accel * delta_time;
                 *vel += added_vel;
+                *angular_vel += torque * inv_inertia * delta_time;
 
-                let added_pos: Vector2 = *vel * delta_time;
-                self.move_relative(&added_pos);
+                // exact solution of dv/dt = -damping * v, independent of the step size
+                *vel *= (-linear_damping * delta_time).exp();
+                *angular_vel *= (-angular_damping * delta_time).exp();
 
-                let added_rotation: f32 = 0.; // 3. * delta_time;
-                self.obj.rotation += added_rotation;
+                self.obj.pos += *vel * delta_time;
+                self.obj.rotation += *angular_vel * delta_time;
 
-                // update corner rotation
-                for corner in &mut self.polygon.corners {
-                    let new_d_vector: Vector2 = (*corner - self.obj.pos).rotated(added_rotation);
-                    *corner = self.obj.pos + new_d_vector;
-                }
+                self.update_colliders();
+            }
+            Kinematic { vel, angular_vel } => {
+                self.obj.pos += vel * delta_time;
+                self.obj.rotation += angular_vel * delta_time;
+                self.update_colliders();
             }
             Static => {}
         }
     }
 
+    /// force at the center of mass, acts during the next physics step and is then cleared.
+    /// Forces applied in several frames before a step add up
+    pub fn apply_force(&mut self, force: Vector2) {
+        self.physics.add_force(force, Vector2::zero());
+    }
+    /// force at `point` in world space, acts during the next physics step
+    pub fn apply_force_at_point(&mut self, force: Vector2, point: Vector2) {
+        self.physics.add_force(force, point - self.obj.pos);
+    }
+    /// acts during the next physics step
+    pub fn apply_torque(&mut self, torque: f32) {
+        self.physics.add_torque(torque);
+    }
+    /// instant change of momentum at the center of mass, also wakes the object up
+    pub fn apply_impulse(&mut self, impulse: Vector2) {
+        self.physics.wake_up();
+        self.physics.add_impulse(impulse, Vector2::zero());
+    }
+    /// instant change of momentum at `point` in world space, also wakes the object up
+    pub fn apply_impulse_at_point(&mut self, impulse: Vector2, point: Vector2) {
+        self.physics.wake_up();
+        self.physics.add_impulse(impulse, point - self.obj.pos);
+    }
+
     pub fn move_relative(&mut self, added_pos: &Vector2) {
+        self.correct_position(added_pos);
+        self.physics.wake_up();
+    }
+    /// like `move_relative`, but without waking the object up, used by the solver
+    pub fn correct_position(&mut self, added_pos: &Vector2) {
         self.obj.pos += *added_pos;
-        self.polygon.move_relative(added_pos);
+        for collider in &mut self.colliders {
+            collider.shape.move_relative(added_pos);
+        }
+    }
+    /// teleports the object, also wakes it up. The previous transform is set too,
+    /// so that the rendering doesn't interpolate between the old and the new place
+    pub fn set_transform(&mut self, pos: Vector2, rotation: f32) {
+        self.obj.pos = pos;
+        self.obj.rotation = rotation;
+        self.save_previous_transform();
+        self.update_colliders();
+        self.physics.wake_up();
+    }
+    /// recomputes the world space geometry of the colliders from the object transform
+    pub fn update_colliders(&mut self) {
+        for collider in &mut self.colliders {
+            collider.update_transform(self.obj.pos, self.obj.rotation);
+        }
+    }
+    pub fn is_sleeping(&self) -> bool {
+        self.physics.is_sleeping()
     }
-    pub fn render(&self, d: &mut RaylibDrawHandle) {
-        self.polygon.render(d, self.obj.color);
+    pub fn save_previous_transform(&mut self) {
+        self.obj.prev_pos = self.obj.pos;
+        self.obj.prev_rotation = self.obj.rotation;
+    }
+
+    /// `alpha` (0..1) interpolates between the previous and the current transform
+    pub fn get_interpolated_transform(&self, alpha: f32) -> (Vector2, f32) {
+        let pos: Vector2 = self.obj.prev_pos.lerp(self.obj.pos, alpha);
+        let rotation: f32 =
+            self.obj.prev_rotation + (self.obj.rotation - self.obj.prev_rotation) * alpha;
+        (pos, rotation)
+    }
+
+    /// `alpha` (0..1) interpolates between the previous and the current transform
+    pub fn render(&self, commands: &mut Vec<DrawCommand>, alpha: f32) {
+        let (pos, rotation) = self.get_interpolated_transform(alpha);
+        for collider in &self.colliders {
+            collider.render(commands, pos, rotation, self.obj.color);
+        }
     }
 }
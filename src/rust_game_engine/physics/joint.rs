@@ -0,0 +1,1013 @@
+use crate::rust_game_engine::backend::DrawCommand;
+use crate::rust_game_engine::color::Color;
+use crate::rust_game_engine::constants::*;
+use crate::rust_game_engine::engine_core::Scene;
+use crate::rust_game_engine::math::Vector2;
+use crate::rust_game_engine::object_store::{ObjectId, ObjectStore};
+use crate::rust_game_engine::physics::game_object::PhysicsObject;
+use crate::rust_game_engine::physics::vector_math::*;
+use std::f32::consts::FRAC_PI_2;
+
+/// What the second end of a joint is attached to
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum JointTarget {
+    /// anchor relative to the object position, before the object rotation
+    Object(ObjectId, Vector2),
+    /// fixed point in world space
+    World(Vector2),
+}
+
+/// drives a joint at `speed` (rad/s or px/s of a relative to b) with at most `max_force`,
+/// a torque for rotating motors
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct JointMotor {
+    pub speed: f32,
+    pub max_force: f32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum JointKind {
+    /// keeps the anchors `length` apart, like a rod.
+    /// With a stiffness above 0 it becomes a spring, damping is in the same units as for forces
+    Distance {
+        length: f32,
+        stiffness: f32,
+        damping: f32,
+    },
+    /// the anchors can't be further apart than `max_length`, but can get closer
+    Rope { max_length: f32 },
+    /// hinge, the anchors stay on top of each other and the objects rotate freely around them
+    Revolute {
+        /// rotation of a minus rotation of b at which the joint angle is 0
+        reference_angle: f32,
+        /// lower and upper joint angle in radians
+        limits: Option<(f32, f32)>,
+        motor: Option<JointMotor>,
+    },
+    /// slider, a can only move along the axis of b and doesn't rotate relative to b
+    Prismatic {
+        /// unit vector in the rotation of b, in world space for joints attached to the world
+        local_axis: Vector2,
+        reference_angle: f32,
+        /// lower and upper translation of a along the axis, 0 is where the anchors meet
+        limits: Option<(f32, f32)>,
+        motor: Option<JointMotor>,
+    },
+    /// rigidly fixes a to b. A stiffness above 0 makes the rotation soft, damping like for torques
+    Weld {
+        reference_angle: f32,
+        stiffness: f32,
+        damping: f32,
+    },
+    /// a can rotate freely and is held on the axis of b by a spring, like a wheel on a car.
+    /// The motor drives the rotation
+    Wheel {
+        /// unit vector in the rotation of b, in world space for joints attached to the world
+        local_axis: Vector2,
+        stiffness: f32,
+        damping: f32,
+        motor: Option<JointMotor>,
+    },
+    /// pulls anchor a towards the world point of b like a spring with at most `max_force`,
+    /// so that contacts still win. Used to drag objects around, see `Scene::start_drag`
+    Mouse {
+        stiffness: f32,
+        damping: f32,
+        max_force: f32,
+    },
+}
+
+/// One scalar constraint of a joint. The relative velocity along `linear` plus the angular
+/// velocities weighted by `angular_a` and `angular_b` is driven towards `-bias`
+#[derive(Clone, Copy, Debug)]
+pub struct JointRow {
+    pub linear: Vector2,
+    pub angular_a: f32,
+    pub angular_b: f32,
+    pub mass: f32,
+    pub bias: f32,
+    /// softness of springs, 0 for rigid rows
+    pub gamma: f32,
+    /// accumulated, kept between steps to warm start the solver
+    pub impulse: f32,
+    pub min_impulse: f32,
+    pub max_impulse: f32,
+}
+
+impl JointRow {
+    /// along `axis` between points at `arm_a` and `arm_b` relative to the object positions
+    pub fn new_linear(axis: Vector2, arm_a: Vector2, arm_b: Vector2) -> JointRow {
+        JointRow {
+            linear: axis,
+            angular_a: cross(arm_a, axis),
+            angular_b: cross(arm_b, axis),
+            mass: 0.,
+            bias: 0.,
+            gamma: 0.,
+            impulse: 0.,
+            min_impulse: f32::NEG_INFINITY,
+            max_impulse: f32::INFINITY,
+        }
+    }
+
+    /// rotation of b relative to a, `sign` -1 for a relative to b
+    pub fn new_angular(sign: f32) -> JointRow {
+        JointRow {
+            angular_a: sign,
+            angular_b: sign,
+            ..JointRow::new_linear(Vector2::zero(), Vector2::zero(), Vector2::zero())
+        }
+    }
+
+    /// rigid, `error` is the value of the constraint that should be 0
+    pub fn with_error(mut self, error: f32, delta_time: f32) -> JointRow {
+        self.bias = JOINT_BAUMGARTE / delta_time * error;
+        self
+    }
+
+    /// soft version of `with_error`, behaves like a damped spring independent of the step size
+    pub fn with_spring(
+        mut self,
+        error: f32,
+        stiffness: f32,
+        damping: f32,
+        delta_time: f32,
+    ) -> JointRow {
+        let softness: f32 = delta_time * (damping + delta_time * stiffness);
+        self.gamma = if softness > 0. { 1. / softness } else { 0. };
+        self.bias = error * delta_time * stiffness * self.gamma;
+        self
+    }
+
+    /// `separation` has to stay positive, it may shrink until it reaches 0
+    pub fn with_limit(mut self, separation: f32, delta_time: f32) -> JointRow {
+        self.bias = if separation > 0. {
+            separation / delta_time
+        } else {
+            JOINT_BAUMGARTE / delta_time * separation
+        };
+        self.min_impulse = 0.;
+        self
+    }
+
+    pub fn with_motor(mut self, speed: f32, max_impulse: f32) -> JointRow {
+        self.bias = -speed;
+        self.min_impulse = -max_impulse;
+        self.max_impulse = max_impulse;
+        self
+    }
+
+    fn get_velocity(&self, a: &PhysicsObject, b: Option<&PhysicsObject>) -> f32 {
+        let (vel_b, angular_vel_b): (Vector2, f32) = match b {
+            Some(b) => (b.physics.get_vel(), b.physics.get_angular_vel()),
+            None => (Vector2::zero(), 0.),
+        };
+        self.linear.dot(vel_b - a.physics.get_vel()) + self.angular_b * angular_vel_b
+            - self.angular_a * a.physics.get_angular_vel()
+    }
+
+    fn apply_impulse(&self, impulse: f32, a: &mut PhysicsObject, b: Option<&mut PhysicsObject>) {
+        a.physics
+            .add_impulse(self.linear * -impulse, Vector2::zero());
+        a.physics.add_angular_impulse(self.angular_a * -impulse);
+        if let Some(b) = b {
+            b.physics
+                .add_impulse(self.linear * impulse, Vector2::zero());
+            b.physics.add_angular_impulse(self.angular_b * impulse);
+        }
+    }
+}
+
+/// Constraint between an object and another object or a point in the world,
+/// solved together with the contacts
+pub struct Joint {
+    pub a: ObjectId,
+    /// relative to the position of a, before its rotation
+    pub local_anchor_a: Vector2,
+    pub b: JointTarget,
+    pub kind: JointKind,
+    /// contacts between a and b are ignored unless this is set
+    pub collide_connected: bool,
+
+    /// solver data, set up in `Joint::pre_step`
+    pub rows: Vec<JointRow>,
+}
+
+impl Joint {
+    pub fn new(a: ObjectId, local_anchor_a: Vector2, b: JointTarget, kind: JointKind) -> Joint {
+        Joint {
+            a,
+            local_anchor_a,
+            b,
+            kind,
+            collide_connected: false,
+            rows: Vec::new(),
+        }
+    }
+
+    /// rigid rod, see `with_spring` to make it elastic
+    pub fn new_distance(
+        a: ObjectId,
+        local_anchor_a: Vector2,
+        b: JointTarget,
+        length: f32,
+    ) -> Joint {
+        let kind: JointKind = JointKind::Distance {
+            length,
+            stiffness: 0.,
+            damping: 0.,
+        };
+        Joint::new(a, local_anchor_a, b, kind)
+    }
+
+    pub fn new_rope(
+        a: ObjectId,
+        local_anchor_a: Vector2,
+        b: JointTarget,
+        max_length: f32,
+    ) -> Joint {
+        Joint::new(a, local_anchor_a, b, JointKind::Rope { max_length })
+    }
+
+    /// hinge, see `Scene::new_revolute_joint` to create one from a point in world space
+    pub fn new_revolute(
+        a: ObjectId,
+        local_anchor_a: Vector2,
+        b: JointTarget,
+        reference_angle: f32,
+    ) -> Joint {
+        let kind: JointKind = JointKind::Revolute {
+            reference_angle,
+            limits: None,
+            motor: None,
+        };
+        Joint::new(a, local_anchor_a, b, kind)
+    }
+
+    /// slider, see `Scene::new_prismatic_joint` to create one from points in world space
+    pub fn new_prismatic(
+        a: ObjectId,
+        local_anchor_a: Vector2,
+        b: JointTarget,
+        local_axis: Vector2,
+        reference_angle: f32,
+    ) -> Joint {
+        let kind: JointKind = JointKind::Prismatic {
+            local_axis: local_axis.normalized(),
+            reference_angle,
+            limits: None,
+            motor: None,
+        };
+        Joint::new(a, local_anchor_a, b, kind)
+    }
+
+    /// see `Scene::new_weld_joint` to create one from a point in world space
+    pub fn new_weld(
+        a: ObjectId,
+        local_anchor_a: Vector2,
+        b: JointTarget,
+        reference_angle: f32,
+    ) -> Joint {
+        let kind: JointKind = JointKind::Weld {
+            reference_angle,
+            stiffness: 0.,
+            damping: 0.,
+        };
+        Joint::new(a, local_anchor_a, b, kind)
+    }
+
+    /// wheel a on b, see `Scene::new_wheel_joint` to create one from points in world space.
+    /// Without `with_spring` the suspension is rigid
+    pub fn new_wheel(
+        a: ObjectId,
+        local_anchor_a: Vector2,
+        b: JointTarget,
+        local_axis: Vector2,
+    ) -> Joint {
+        let kind: JointKind = JointKind::Wheel {
+            local_axis: local_axis.normalized(),
+            stiffness: 0.,
+            damping: 0.,
+            motor: None,
+        };
+        Joint::new(a, local_anchor_a, b, kind)
+    }
+
+    /// drags a towards `target` in world space
+    pub fn new_mouse(
+        a: ObjectId,
+        local_anchor_a: Vector2,
+        target: Vector2,
+        stiffness: f32,
+        damping: f32,
+        max_force: f32,
+    ) -> Joint {
+        let kind: JointKind = JointKind::Mouse {
+            stiffness,
+            damping,
+            max_force,
+        };
+        Joint::new(a, local_anchor_a, JointTarget::World(target), kind)
+    }
+
+    /// turns a distance joint into a spring, softens the rotation of a weld joint and
+    /// sets the suspension of a wheel joint, has no effect on other joints
+    pub fn with_spring(mut self, stiffness: f32, damping: f32) -> Joint {
+        match self.kind {
+            JointKind::Distance {
+                stiffness: ref mut k,
+                damping: ref mut d,
+                ..
+            }
+            | JointKind::Weld {
+                stiffness: ref mut k,
+                damping: ref mut d,
+                ..
+            }
+            | JointKind::Wheel {
+                stiffness: ref mut k,
+                damping: ref mut d,
+                ..
+            } => {
+                *k = stiffness;
+                *d = damping;
+            }
+            _ => {}
+        }
+        self
+    }
+
+    /// lower and upper joint angle of a revolute joint or translation of a prismatic joint,
+    /// has no effect on other joints
+    pub fn with_limits(mut self, lower: f32, upper: f32) -> Joint {
+        if let JointKind::Revolute { ref mut limits, .. }
+        | JointKind::Prismatic { ref mut limits, .. } = self.kind
+        {
+            *limits = Some((lower.min(upper), lower.max(upper)));
+        }
+        self
+    }
+
+    /// motor of a revolute, prismatic or wheel joint, has no effect on other joints
+    pub fn with_motor(mut self, speed: f32, max_force: f32) -> Joint {
+        self.set_motor(speed, max_force);
+        self
+    }
+
+    pub fn with_collide_connected(mut self, collide_connected: bool) -> Joint {
+        self.collide_connected = collide_connected;
+        self
+    }
+
+    /// see `Scene::set_joint_motor`, which also wakes the objects up
+    pub fn set_motor(&mut self, speed: f32, max_force: f32) -> bool {
+        if let JointKind::Revolute { ref mut motor, .. }
+        | JointKind::Prismatic { ref mut motor, .. }
+        | JointKind::Wheel { ref mut motor, .. } = self.kind
+        {
+            *motor = Some(JointMotor { speed, max_force });
+            return true;
+        }
+        false
+    }
+
+    pub fn involves(&self, id: ObjectId) -> bool {
+        self.a == id || self.get_object_b() == Some(id)
+    }
+
+    /// a and, unless the joint is attached to the world, b
+    pub fn get_object_ids(&self) -> Vec<ObjectId> {
+        [Some(self.a), self.get_object_b()]
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    pub fn get_object_b(&self) -> Option<ObjectId> {
+        match self.b {
+            JointTarget::Object(id, _) => Some(id),
+            JointTarget::World(_) => None,
+        }
+    }
+
+    /// both objects of the joint, `None` if one of them was removed
+    pub fn get_objects_mut<'a>(
+        &self,
+        game_objects: &'a mut ObjectStore<PhysicsObject>,
+    ) -> Option<(&'a mut PhysicsObject, Option<&'a mut PhysicsObject>)> {
+        match self.b {
+            JointTarget::Object(b, _) => {
+                let (a, b) = game_objects.get_pair_mut(self.a, b)?;
+                Some((a, Some(b)))
+            }
+            JointTarget::World(_) => Some((game_objects.get_mut(self.a)?, None)),
+        }
+    }
+
+    /// anchors in world space for the given transforms of a and b
+    pub fn get_anchors(
+        &self,
+        (pos_a, rotation_a): (Vector2, f32),
+        transform_b: Option<(Vector2, f32)>,
+    ) -> (Vector2, Vector2) {
+        let anchor_a: Vector2 = pos_a + self.local_anchor_a.rotated(rotation_a);
+        let anchor_b: Vector2 = match (self.b, transform_b) {
+            (JointTarget::Object(_, local), Some((pos_b, rotation_b))) => {
+                pos_b + local.rotated(rotation_b)
+            }
+            (JointTarget::World(point), _) => point,
+            // object b is missing, only happens while it is being removed
+            (JointTarget::Object(_, local), None) => local,
+        };
+        (anchor_a, anchor_b)
+    }
+
+    /// rotation of a relative to b minus the reference angle of the joint kinds that have one.
+    /// Joints attached to the world use a rotation of 0 for b
+    pub fn get_angle(&self, rotation_a: f32, rotation_b: f32) -> f32 {
+        let reference_angle: f32 = match self.kind {
+            JointKind::Revolute {
+                reference_angle, ..
+            }
+            | JointKind::Prismatic {
+                reference_angle, ..
+            }
+            | JointKind::Weld {
+                reference_angle, ..
+            } => reference_angle,
+            _ => 0.,
+        };
+        rotation_a - rotation_b - reference_angle
+    }
+
+    /// sets up the rows for the current positions, then applies the impulses of the last step again
+    pub fn pre_step(
+        &mut self,
+        a: &mut PhysicsObject,
+        mut b: Option<&mut PhysicsObject>,
+        delta_time: f32,
+    ) {
+        let transform_b: Option<(Vector2, f32)> = b.as_ref().map(|b| (b.obj.pos, b.obj.rotation));
+        let (anchor_a, anchor_b) = self.get_anchors((a.obj.pos, a.obj.rotation), transform_b);
+        let offset: Vector2 = anchor_b - anchor_a;
+        let distance: f32 = offset.length();
+        let axis: Vector2 = if distance > f32::EPSILON {
+            offset / distance
+        } else {
+            Vector2::zero()
+        };
+        let r_a: Vector2 = anchor_a - a.obj.pos;
+        let r_b: Vector2 = match &b {
+            Some(b) => anchor_b - b.obj.pos,
+            None => Vector2::zero(),
+        };
+        let rotation_b: f32 = transform_b.map_or(0., |(_, rotation)| rotation);
+        let angle: f32 = self.get_angle(a.obj.rotation, rotation_b);
+        // rows along an axis that turns with b, measuring the position of anchor a on it
+        let slider_row = |local_axis: Vector2| -> (JointRow, f32) {
+            let axis: Vector2 = local_axis.rotated(rotation_b);
+            let row: JointRow = JointRow::new_linear(-axis, r_a, r_b - offset);
+            (row, axis.dot(-offset))
+        };
+
+        let mut rows: Vec<JointRow> = Vec::new();
+        match self.kind {
+            JointKind::Distance {
+                length,
+                stiffness,
+                damping,
+            } => {
+                let row: JointRow = JointRow::new_linear(axis, r_a, r_b);
+                rows.push(if stiffness > 0. {
+                    row.with_spring(distance - length, stiffness, damping, delta_time)
+                } else {
+                    row.with_error(distance - length, delta_time)
+                });
+            }
+            JointKind::Rope { max_length } => {
+                // ropes can only pull
+                rows.push(
+                    JointRow::new_linear(-axis, r_a, r_b)
+                        .with_limit(max_length - distance, delta_time),
+                );
+            }
+            JointKind::Revolute { limits, motor, .. } => {
+                if let Some(JointMotor { speed, max_force }) = motor {
+                    rows.push(JointRow::new_angular(-1.).with_motor(speed, max_force * delta_time));
+                }
+                if let Some((lower, upper)) = limits {
+                    rows.push(JointRow::new_angular(-1.).with_limit(angle - lower, delta_time));
+                    rows.push(JointRow::new_angular(1.).with_limit(upper - angle, delta_time));
+                }
+                for (axis, error) in [
+                    (Vector2::new(1., 0.), offset.x),
+                    (Vector2::new(0., 1.), offset.y),
+                ] {
+                    rows.push(JointRow::new_linear(axis, r_a, r_b).with_error(error, delta_time));
+                }
+            }
+            JointKind::Prismatic {
+                local_axis,
+                limits,
+                motor,
+                ..
+            } => {
+                let (row, translation) = slider_row(local_axis);
+                if let Some(JointMotor { speed, max_force }) = motor {
+                    rows.push(row.with_motor(speed, max_force * delta_time));
+                }
+                if let Some((lower, upper)) = limits {
+                    rows.push(row.with_limit(translation - lower, delta_time));
+                    let (row, _) = slider_row(-local_axis);
+                    rows.push(row.with_limit(upper - translation, delta_time));
+                }
+                rows.push(JointRow::new_angular(-1.).with_error(angle, delta_time));
+                let (row, error) = slider_row(local_axis.rotated(FRAC_PI_2));
+                rows.push(row.with_error(error, delta_time));
+            }
+            JointKind::Weld {
+                stiffness, damping, ..
+            } => {
+                let row: JointRow = JointRow::new_angular(-1.);
+                rows.push(if stiffness > 0. {
+                    row.with_spring(angle, stiffness, damping, delta_time)
+                } else {
+                    row.with_error(angle, delta_time)
+                });
+                for (axis, error) in [
+                    (Vector2::new(1., 0.), offset.x),
+                    (Vector2::new(0., 1.), offset.y),
+                ] {
+                    rows.push(JointRow::new_linear(axis, r_a, r_b).with_error(error, delta_time));
+                }
+            }
+            JointKind::Mouse {
+                stiffness,
+                damping,
+                max_force,
+            } => {
+                // limit of the combined impulse of both rows, see `solve_mouse_velocity`
+                let max_impulse: f32 = max_force * delta_time;
+                for (axis, error) in [
+                    (Vector2::new(1., 0.), offset.x),
+                    (Vector2::new(0., 1.), offset.y),
+                ] {
+                    let mut row: JointRow = JointRow::new_linear(axis, r_a, r_b)
+                        .with_spring(error, stiffness, damping, delta_time);
+                    row.min_impulse = -max_impulse;
+                    row.max_impulse = max_impulse;
+                    rows.push(row);
+                }
+            }
+            JointKind::Wheel {
+                local_axis,
+                stiffness,
+                damping,
+                motor,
+            } => {
+                if let Some(JointMotor { speed, max_force }) = motor {
+                    rows.push(JointRow::new_angular(-1.).with_motor(speed, max_force * delta_time));
+                }
+                let (row, translation) = slider_row(local_axis);
+                rows.push(if stiffness > 0. {
+                    row.with_spring(translation, stiffness, damping, delta_time)
+                } else {
+                    row.with_error(translation, delta_time)
+                });
+                let (row, error) = slider_row(local_axis.rotated(FRAC_PI_2));
+                rows.push(row.with_error(error, delta_time));
+            }
+        }
+
+        let (_, m_a_inv): (f32, f32) = a.physics.get_masses();
+        let (_, i_a_inv): (f32, f32) = a.physics.get_inertias();
+        let (m_b_inv, i_b_inv): (f32, f32) = match &b {
+            Some(b) => (b.physics.get_masses().1, b.physics.get_inertias().1),
+            None => (0., 0.),
+        };
+        // the same rows as in the last step, unless the joint was changed in between
+        let is_warm_start: bool = rows.len() == self.rows.len();
+        for (k, row) in rows.iter_mut().enumerate() {
+            let inv_mass: f32 = (m_a_inv + m_b_inv) * row.linear.length_sqr()
+                + i_a_inv * row.angular_a * row.angular_a
+                + i_b_inv * row.angular_b * row.angular_b
+                + row.gamma;
+            row.mass = if inv_mass > 0. { 1. / inv_mass } else { 0. };
+
+            if is_warm_start {
+                row.impulse = self.rows[k].impulse.clamp(row.min_impulse, row.max_impulse);
+                row.apply_impulse(row.impulse, a, b.as_deref_mut());
+            }
+        }
+        self.rows = rows;
+    }
+
+    /// one iteration of sequential impulses
+    pub fn solve_velocity(&mut self, a: &mut PhysicsObject, mut b: Option<&mut PhysicsObject>) {
+        if let JointKind::Mouse { .. } = self.kind {
+            self.solve_mouse_velocity(a, b);
+            return;
+        }
+        for row in &mut self.rows {
+            let vel: f32 = row.get_velocity(a, b.as_deref());
+            let lambda: f32 = -row.mass * (vel + row.bias + row.gamma * row.impulse);
+            let new_impulse: f32 = (row.impulse + lambda).clamp(row.min_impulse, row.max_impulse);
+            row.apply_impulse(new_impulse - row.impulse, a, b.as_deref_mut());
+            row.impulse = new_impulse;
+        }
+    }
+
+    /// the x and y rows of a mouse joint share one force limit: the accumulated impulse is clamped
+    /// by its length, so that a diagonal pull isn't stronger than a straight one
+    fn solve_mouse_velocity(&mut self, a: &mut PhysicsObject, mut b: Option<&mut PhysicsObject>) {
+        let [row_x, row_y] = &mut self.rows[..] else {
+            return;
+        };
+        let old_impulse: Vector2 = Vector2::new(row_x.impulse, row_y.impulse);
+        let vel: Vector2 = Vector2::new(
+            row_x.get_velocity(a, b.as_deref()),
+            row_y.get_velocity(a, b.as_deref()),
+        );
+        let lambda: Vector2 = Vector2::new(
+            -row_x.mass * (vel.x + row_x.bias + row_x.gamma * row_x.impulse),
+            -row_y.mass * (vel.y + row_y.bias + row_y.gamma * row_y.impulse),
+        );
+        let mut new_impulse: Vector2 = old_impulse + lambda;
+        let max_impulse: f32 = row_x.max_impulse;
+        if new_impulse.length_sqr() > max_impulse * max_impulse {
+            new_impulse = new_impulse.normalized() * max_impulse;
+        }
+        row_x.apply_impulse(new_impulse.x - old_impulse.x, a, b.as_deref_mut());
+        row_y.apply_impulse(new_impulse.y - old_impulse.y, a, b);
+        row_x.impulse = new_impulse.x;
+        row_y.impulse = new_impulse.y;
+    }
+
+    /// line between the anchors for the given object transforms, hinges and welds are drawn as a cross
+    pub fn render(
+        &self,
+        commands: &mut Vec<DrawCommand>,
+        transform_a: (Vector2, f32),
+        transform_b: Option<(Vector2, f32)>,
+    ) {
+        let (anchor_a, anchor_b) = self.get_anchors(transform_a, transform_b);
+        let lines: Vec<(Vector2, Vector2)> = match self.kind {
+            JointKind::Revolute { .. } | JointKind::Weld { .. } => {
+                let size: f32 = 4.;
+                vec![
+                    (
+                        anchor_a - Vector2::new(size, size),
+                        anchor_a + Vector2::new(size, size),
+                    ),
+                    (
+                        anchor_a - Vector2::new(size, -size),
+                        anchor_a + Vector2::new(size, -size),
+                    ),
+                ]
+            }
+            _ => vec![(anchor_a, anchor_b)],
+        };
+        for (start, end) in lines {
+            commands.push(DrawCommand::Line {
+                start,
+                end,
+                thickness: 2.,
+                color: Color::DARKGRAY,
+            });
+        }
+    }
+}
+
+impl Scene {
+    /// hinge between a and b (or the world) at `anchor` in world space, the current rotation
+    /// of a relative to b becomes the joint angle 0. `None` if one of the objects doesn't exist
+    pub fn new_revolute_joint(
+        &self,
+        a: ObjectId,
+        b: Option<ObjectId>,
+        anchor: Vector2,
+    ) -> Option<Joint> {
+        let (local_anchor_a, target, reference_angle, _) = self.get_joint_frame(a, b, anchor)?;
+        Some(Joint::new_revolute(
+            a,
+            local_anchor_a,
+            target,
+            reference_angle,
+        ))
+    }
+
+    /// slider that lets a move along `axis` (world space) relative to b or the world, the
+    /// translation 0 is at `anchor`. `None` if one of the objects doesn't exist
+    pub fn new_prismatic_joint(
+        &self,
+        a: ObjectId,
+        b: Option<ObjectId>,
+        anchor: Vector2,
+        axis: Vector2,
+    ) -> Option<Joint> {
+        let (local_anchor_a, target, reference_angle, rotation_b) =
+            self.get_joint_frame(a, b, anchor)?;
+        let local_axis: Vector2 = axis.rotated(-rotation_b);
+        Some(Joint::new_prismatic(
+            a,
+            local_anchor_a,
+            target,
+            local_axis,
+            reference_angle,
+        ))
+    }
+
+    /// fixes a to b or the world in their current relative position, `None` if one of the
+    /// objects doesn't exist
+    pub fn new_weld_joint(
+        &self,
+        a: ObjectId,
+        b: Option<ObjectId>,
+        anchor: Vector2,
+    ) -> Option<Joint> {
+        let (local_anchor_a, target, reference_angle, _) = self.get_joint_frame(a, b, anchor)?;
+        Some(Joint::new_weld(a, local_anchor_a, target, reference_angle))
+    }
+
+    /// wheel a that rotates around `anchor` and moves along `axis` (world space) relative to
+    /// the chassis b, e.g. straight down. `None` if one of the objects doesn't exist
+    pub fn new_wheel_joint(
+        &self,
+        a: ObjectId,
+        b: ObjectId,
+        anchor: Vector2,
+        axis: Vector2,
+    ) -> Option<Joint> {
+        let (local_anchor_a, target, _, rotation_b) = self.get_joint_frame(a, Some(b), anchor)?;
+        let local_axis: Vector2 = axis.rotated(-rotation_b);
+        Some(Joint::new_wheel(a, local_anchor_a, target, local_axis))
+    }
+
+    /// anchor of a, target at `anchor`, rotation of a relative to b and rotation of b
+    fn get_joint_frame(
+        &self,
+        a: ObjectId,
+        b: Option<ObjectId>,
+        anchor: Vector2,
+    ) -> Option<(Vector2, JointTarget, f32, f32)> {
+        let (local_anchor_a, rotation_a) = self.get_local_point(a, anchor)?;
+        let (target, rotation_b): (JointTarget, f32) = match b {
+            Some(b) => {
+                let (local_anchor_b, rotation_b) = self.get_local_point(b, anchor)?;
+                (JointTarget::Object(b, local_anchor_b), rotation_b)
+            }
+            None => (JointTarget::World(anchor), 0.),
+        };
+        Some((local_anchor_a, target, rotation_a - rotation_b, rotation_b))
+    }
+
+    /// the point relative to the object position before its rotation, and the object rotation
+    fn get_local_point(&self, id: ObjectId, point: Vector2) -> Option<(Vector2, f32)> {
+        let obj: &PhysicsObject = self.game_objects.get(id)?;
+        Some((
+            (point - obj.obj.pos).rotated(-obj.obj.rotation),
+            obj.obj.rotation,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rust_game_engine::physics::material::Material;
+    use crate::rust_game_engine::physics::physics_addition::Polygon;
+    use crate::rust_game_engine::physics::shape::Shape;
+
+    const STEP: f32 = 1. / 60.;
+
+    fn add_ball(scene: &mut Scene, pos: Vector2) -> ObjectId {
+        let ball: PhysicsObject =
+            PhysicsObject::new_circle(pos, 10., Material::default(), "ball".to_string());
+        scene.add_game_object(ball)
+    }
+
+    /// horizontal bar from `start` to `end`
+    fn add_bar(scene: &mut Scene, start: Vector2, end: Vector2) -> ObjectId {
+        let bar: PhysicsObject = PhysicsObject::new_with_shape(
+            (start + end) / 2.,
+            Shape::Polygon(Polygon::new_polygon_line(start, end, 5.)),
+            Material::default(),
+            "bar".to_string(),
+        );
+        scene.add_game_object(bar)
+    }
+
+    #[test]
+    fn pendulums_keep_their_length() {
+        let mut scene: Scene = Scene::new_headless(STEP);
+        let pivot: Vector2 = Vector2::new(500., 300.);
+        let rod_ball: ObjectId = add_ball(&mut scene, Vector2::new(600., 300.));
+        scene.add_joint(Joint::new_distance(
+            rod_ball,
+            Vector2::zero(),
+            JointTarget::World(pivot),
+            100.,
+        ));
+        // starts closer than the rope is long and falls until it is tight
+        let rope_ball: ObjectId = add_ball(&mut scene, Vector2::new(450., 300.));
+        scene.add_joint(Joint::new_rope(
+            rope_ball,
+            Vector2::zero(),
+            JointTarget::World(pivot),
+            100.,
+        ));
+
+        let mut max_rope_length: f32 = 0.;
+        for _ in 0..180 {
+            scene.physics_step(STEP);
+            let rod_length: f32 =
+                (scene.get_game_object(rod_ball).unwrap().obj.pos - pivot).length();
+            assert!((rod_length - 100.).abs() < 1., "rod is {} long", rod_length);
+            let rope_length: f32 =
+                (scene.get_game_object(rope_ball).unwrap().obj.pos - pivot).length();
+            assert!(rope_length < 101., "rope is {} long", rope_length);
+            max_rope_length = max_rope_length.max(rope_length);
+        }
+        assert!(max_rope_length > 99., "the rope never got tight");
+    }
+
+    #[test]
+    fn revolute_limit_holds_and_motor_reaches_its_speed() {
+        let mut scene: Scene = Scene::new_headless(STEP);
+        let pivot: Vector2 = Vector2::new(500., 300.);
+        // gravity turns the bar down, clockwise on screen
+        let bar: ObjectId = add_bar(&mut scene, pivot, Vector2::new(600., 300.));
+        let joint: Joint = scene
+            .new_revolute_joint(bar, None, pivot)
+            .unwrap()
+            .with_limits(-0.5, 0.5);
+        scene.add_joint(joint);
+        for _ in 0..180 {
+            scene.physics_step(STEP);
+            let rotation: f32 = scene.get_game_object(bar).unwrap().obj.rotation;
+            assert!(rotation < 0.52, "bar turned to {}", rotation);
+        }
+        let rotation: f32 = scene.get_game_object(bar).unwrap().obj.rotation;
+        assert!(rotation > 0.45, "bar hangs at {}", rotation);
+
+        let mut scene: Scene = Scene::new_headless(STEP);
+        scene.disable_gravity();
+        let wheel: ObjectId = add_ball(&mut scene, pivot);
+        let joint: Joint = scene
+            .new_revolute_joint(wheel, None, pivot)
+            .unwrap()
+            .with_motor(3., 1e6);
+        scene.add_joint(joint);
+        for _ in 0..60 {
+            scene.physics_step(STEP);
+        }
+        let angular_vel: f32 = scene
+            .get_game_object(wheel)
+            .unwrap()
+            .physics
+            .get_angular_vel();
+        assert!(
+            (angular_vel - 3.).abs() < 0.01,
+            "wheel turns at {}",
+            angular_vel
+        );
+    }
+
+    #[test]
+    fn prismatic_body_stays_on_its_axis_within_its_limits() {
+        let mut scene: Scene = Scene::new_headless(STEP);
+        let anchor: Vector2 = Vector2::new(500., 300.);
+        // gravity slides it down the slope until the upper limit
+        let axis: Vector2 = Vector2::new(1., 1.).normalized();
+        let slider: ObjectId = add_bar(
+            &mut scene,
+            Vector2::new(470., 300.),
+            Vector2::new(530., 300.),
+        );
+        let joint: Joint = scene
+            .new_prismatic_joint(slider, None, anchor, axis)
+            .unwrap()
+            .with_limits(-20., 50.);
+        scene.add_joint(joint);
+
+        for _ in 0..180 {
+            scene.physics_step(STEP);
+            let obj: &PhysicsObject = scene.get_game_object(slider).unwrap();
+            let offset: Vector2 = obj.obj.pos - anchor;
+            let off_axis: f32 = cross(axis, offset);
+            assert!(off_axis.abs() < 0.5, "slider left the axis by {}", off_axis);
+            assert!(
+                obj.obj.rotation.abs() < 0.01,
+                "slider turned to {}",
+                obj.obj.rotation
+            );
+            assert!(offset.dot(axis) < 51., "slider moved to {:?}", obj.obj.pos);
+        }
+        let offset: Vector2 = scene.get_game_object(slider).unwrap().obj.pos - anchor;
+        assert!(offset.dot(axis) > 49., "slider stopped at {:?}", offset);
+    }
+
+    #[test]
+    fn weld_keeps_the_relative_angle() {
+        let mut scene: Scene = Scene::new_headless(STEP);
+        // the bar sticks out of the ball to the right and pulls it around the pivot
+        let pivot: Vector2 = Vector2::new(500., 300.);
+        let ball: ObjectId = add_ball(&mut scene, pivot);
+        let bar: ObjectId = add_bar(
+            &mut scene,
+            Vector2::new(510., 300.),
+            Vector2::new(610., 300.),
+        );
+        let hinge: Joint = scene.new_revolute_joint(ball, None, pivot).unwrap();
+        scene.add_joint(hinge);
+        let weld: Joint = scene
+            .new_weld_joint(bar, Some(ball), Vector2::new(510., 300.))
+            .unwrap();
+        scene.add_joint(weld);
+
+        for _ in 0..120 {
+            scene.physics_step(STEP);
+            let rotation_ball: f32 = scene.get_game_object(ball).unwrap().obj.rotation;
+            let rotation_bar: f32 = scene.get_game_object(bar).unwrap().obj.rotation;
+            assert!(
+                (rotation_bar - rotation_ball).abs() < 0.02,
+                "{} and {} are not welded",
+                rotation_bar,
+                rotation_ball
+            );
+        }
+        assert!(scene.get_game_object(ball).unwrap().obj.rotation.abs() > 0.5);
+    }
+
+    #[test]
+    fn removing_an_object_removes_its_joints() {
+        let mut scene: Scene = Scene::new_headless(STEP);
+        let a: ObjectId = add_ball(&mut scene, Vector2::new(400., 300.));
+        let b: ObjectId = add_ball(&mut scene, Vector2::new(500., 300.));
+        let c: ObjectId = add_ball(&mut scene, Vector2::new(600., 300.));
+        let ab: ObjectId = scene.add_joint(Joint::new_distance(
+            a,
+            Vector2::zero(),
+            JointTarget::Object(b, Vector2::zero()),
+            100.,
+        ));
+        let b_to_world: ObjectId = scene.add_joint(Joint::new_rope(
+            b,
+            Vector2::zero(),
+            JointTarget::World(Vector2::new(500., 200.)),
+            100.,
+        ));
+        let ac: ObjectId = scene.add_joint(Joint::new_distance(
+            a,
+            Vector2::zero(),
+            JointTarget::Object(c, Vector2::zero()),
+            200.,
+        ));
+
+        scene.remove_game_object(b);
+        assert!(scene.get_joint(ab).is_none());
+        assert!(scene.get_joint(b_to_world).is_none());
+        assert!(scene.get_joint(ac).is_some());
+        assert_eq!(scene.get_joints().len(), 1);
+        scene.physics_step(STEP);
+    }
+
+    #[test]
+    fn diagonal_mouse_pull_is_limited_by_the_max_force() {
+        let mut scene: Scene = Scene::new_headless(1. / 60.);
+        scene.disable_gravity();
+        let obj: PhysicsObject = PhysicsObject::new_circle(
+            Vector2::new(500., 300.),
+            20.,
+            Material::default(),
+            "obj".to_string(),
+        );
+        let mass: f32 = obj.physics.get_masses().0;
+        let id: ObjectId = scene.add_game_object(obj);
+        let max_acceleration: f32 = 100.;
+        let target: Vector2 = Vector2::new(1500., 1300.);
+        scene.add_joint(Joint::new_mouse(
+            id,
+            Vector2::zero(),
+            target,
+            1e6 * mass,
+            0.,
+            max_acceleration * mass,
+        ));
+
+        let delta_time: f32 = 1. / 60.;
+        scene.physics_step(delta_time);
+        let vel: Vector2 = scene.get_game_object(id).unwrap().physics.get_vel();
+        assert!(
+            vel.length() <= max_acceleration * delta_time * 1.001,
+            "{:?} is too fast",
+            vel
+        );
+        // pulled towards the target
+        assert!(
+            vel.normalized()
+                .dot((target - Vector2::new(500., 300.)).normalized())
+                > 0.99
+        );
+    }
+}
@@ -0,0 +1,87 @@
+/// Physical surface and bulk properties of an object
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Material {
+    /// 0: no bounce, 1: perfectly elastic
+    pub restitution: f32,
+    /// friction coefficient while resting on another object
+    pub static_friction: f32,
+    /// friction coefficient while sliding over another object
+    pub dynamic_friction: f32,
+    /// mass per px², the mass of an object is density * area
+    pub density: f32,
+}
+
+/// How the values of two materials are combined for a contact
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MixRule {
+    Min,
+    Max,
+    Average,
+    Multiply,
+}
+
+/// Mix rules used by the scene for every contact pair
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MaterialMixing {
+    pub restitution: MixRule,
+    pub friction: MixRule,
+}
+
+impl Material {
+    pub const DEFAULT: Material = Material::new(0.9, 0.6, 0.4, 1.);
+    pub const RUBBER: Material = Material::new(0.8, 1., 0.8, 1.2);
+    pub const WOOD: Material = Material::new(0.3, 0.5, 0.3, 0.6);
+    pub const METAL: Material = Material::new(0.2, 0.4, 0.3, 7.8);
+    pub const ICE: Material = Material::new(0.1, 0.05, 0.02, 0.9);
+
+    pub const fn new(
+        restitution: f32,
+        static_friction: f32,
+        dynamic_friction: f32,
+        density: f32,
+    ) -> Material {
+        Material {
+            restitution,
+            static_friction,
+            dynamic_friction,
+            density,
+        }
+    }
+}
+
+impl Default for Material {
+    fn default() -> Self {
+        Material::DEFAULT
+    }
+}
+
+impl MixRule {
+    pub fn mix(&self, a: f32, b: f32) -> f32 {
+        match self {
+            MixRule::Min => a.min(b),
+            MixRule::Max => a.max(b),
+            MixRule::Average => (a + b) / 2.,
+            MixRule::Multiply => a * b,
+        }
+    }
+}
+
+impl MaterialMixing {
+    /// (restitution, static friction, dynamic friction) of a contact between `a` and `b`
+    pub fn mix(&self, a: &Material, b: &Material) -> (f32, f32, f32) {
+        (
+            self.restitution.mix(a.restitution, b.restitution),
+            self.friction.mix(a.static_friction, b.static_friction),
+            self.friction.mix(a.dynamic_friction, b.dynamic_friction),
+        )
+    }
+}
+
+impl Default for MaterialMixing {
+    fn default() -> Self {
+        MaterialMixing {
+            restitution: MixRule::Max,
+            friction: MixRule::Average,
+        }
+    }
+}
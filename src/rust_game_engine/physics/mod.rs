@@ -1,3 +1,19 @@
+pub mod ccd;
+pub mod collider;
+pub mod collision;
+pub mod collision_filter;
 pub mod game_object;
 pub mod scene;
-pub mod physics_addition;
\ No newline at end of file
+pub mod solver_config;
+pub mod physics_addition;
+pub mod vector_math;
+pub mod contact;
+pub mod contact_events;
+pub mod decomposition;
+pub mod joint;
+pub mod material;
+pub mod mouse_drag;
+pub mod sensor;
+pub mod shape;
+pub mod sleep;
+pub mod sleep_config;
\ No newline at end of file
@@ -0,0 +1,110 @@
+use crate::rust_game_engine::constants::*;
+use crate::rust_game_engine::engine_core::Scene;
+use crate::rust_game_engine::math::Vector2;
+use crate::rust_game_engine::object_store::ObjectId;
+use crate::rust_game_engine::physics::joint::{Joint, JointTarget};
+use std::f32::consts::TAU;
+
+/// Settings and state of dragging objects with a mouse joint, see `Scene::drag_with_mouse`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MouseDrag {
+    /// Hz, how fast the object follows the target
+    pub frequency: f32,
+    /// 1 is critically damped
+    pub damping_ratio: f32,
+    /// px/s², the force of the joint is this times the mass of the object
+    pub max_acceleration: f32,
+    /// mouse joint in `Scene::joints` while an object is dragged
+    pub joint: Option<ObjectId>,
+}
+
+impl MouseDrag {
+    pub fn new(frequency: f32, damping_ratio: f32, max_acceleration: f32) -> MouseDrag {
+        MouseDrag {
+            frequency,
+            damping_ratio,
+            max_acceleration,
+            joint: None,
+        }
+    }
+}
+
+impl Default for MouseDrag {
+    fn default() -> Self {
+        MouseDrag::new(DRAG_FREQUENCY, DRAG_DAMPING_RATIO, DRAG_MAX_ACCELERATION)
+    }
+}
+
+impl Scene {
+    /// grabs the dynamic object drawn on top at `point` (world space) and pulls it towards
+    /// the point until `end_drag`. `false` if there is no such object
+    pub fn start_drag(&mut self, point: Vector2) -> bool {
+        self.end_drag();
+        let Some(id) = self.get_objects_at(point).into_iter().rev().find(|&id| {
+            self.game_objects
+                .get(id)
+                .is_some_and(|obj| obj.physics.is_dynamic() && !obj.is_sensor)
+        }) else {
+            return false;
+        };
+        let Some(obj) = self.game_objects.get(id) else {
+            return false;
+        };
+
+        // spring with the same frequency for light and heavy objects
+        let mass: f32 = obj.physics.get_masses().0;
+        let omega: f32 = TAU * self.mouse_drag.frequency;
+        let stiffness: f32 = mass * omega * omega;
+        let damping: f32 = 2. * mass * self.mouse_drag.damping_ratio * omega;
+        let max_force: f32 = mass * self.mouse_drag.max_acceleration;
+
+        let local_anchor: Vector2 = (point - obj.obj.pos).rotated(-obj.obj.rotation);
+        let joint: Joint = Joint::new_mouse(id, local_anchor, point, stiffness, damping, max_force);
+        self.mouse_drag.joint = Some(self.add_joint(joint));
+        true
+    }
+
+    /// moves the target of the dragged object
+    pub fn update_drag(&mut self, point: Vector2) {
+        let Some(joint) = self.mouse_drag.joint.and_then(|id| self.joints.get_mut(id)) else {
+            // the joint was removed together with the dragged object
+            self.mouse_drag.joint = None;
+            return;
+        };
+        if joint.b == JointTarget::World(point) {
+            return;
+        }
+        joint.b = JointTarget::World(point);
+        if let Some(obj) = self.game_objects.get_mut(joint.a) {
+            obj.physics.wake_up();
+        }
+    }
+
+    /// lets go of the dragged object, it keeps its current velocity
+    pub fn end_drag(&mut self) {
+        if let Some(id) = self.mouse_drag.joint.take() {
+            self.remove_joint(id);
+        }
+    }
+
+    pub fn get_dragged_object(&self) -> Option<ObjectId> {
+        let id: ObjectId = self.mouse_drag.joint?;
+        Some(self.joints.get(id)?.a)
+    }
+
+    /// drags objects with the left mouse button, to be called once per frame.
+    /// `true` while an object is dragged
+    pub fn drag_with_mouse(&mut self) -> bool {
+        let point: Vector2 = self.mouse_pos();
+        if !self.mouse_down() {
+            self.end_drag();
+        } else if self.mouse_drag.joint.is_none() {
+            if self.mouse_clicked() {
+                self.start_drag(point);
+            }
+        } else {
+            self.update_drag(point);
+        }
+        self.get_dragged_object().is_some()
+    }
+}
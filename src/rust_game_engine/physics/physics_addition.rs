@@ -1,23 +1,87 @@
-use raylib::drawing::{RaylibDraw, RaylibDrawHandle};
-use raylib::math::{Rectangle, Vector2};
-use raylib::prelude::Color;
+use crate::rust_game_engine::backend::DrawCommand;
+use crate::rust_game_engine::color::Color;
+use crate::rust_game_engine::math::{Rectangle, Vector2};
+use crate::rust_game_engine::physics::decomposition::{self, PolygonError};
+use crate::rust_game_engine::physics::vector_math::*;
 use std::f32::consts::{PI, TAU};
 
 pub struct Polygon {
+    /// corners relative to the object position, without rotation
+    pub local_corners: Vec<Vector2>,
+    /// corners in world space
     pub corners: Vec<Vector2>,
     pub bounding_box: Rectangle,
 }
 pub enum PhysicsAddition {
     Dynamic {
+        /// constant acceleration in addition to the scene gravity
         accel: Vector2,
+        /// multiplies the scene gravity, 0: not affected by gravity
+        gravity_scale: f32,
         vel: Vector2,
         mass: f32,
         inv_mass: f32,
+        /// sum of the forces applied since the last physics step
+        force: Vector2,
+        /// 1/s, fraction of the velocity lost per second is 1 - e^(-linear_damping)
+        linear_damping: f32,
+
+        angular_vel: f32,
+        inertia: f32,
+        inv_inertia: f32,
+        /// sum of the torques applied since the last physics step
+        torque: f32,
+        /// 1/s, like `linear_damping`
+        angular_damping: f32,
+
+        /// sleeping objects are not moved and their contacts are not solved
+        sleeping: bool,
+        /// s the object has been slower than the sleep thresholds
+        sleep_time: f32,
+    },
+    /// infinite mass, moved only by its velocity, pushes dynamic objects but is never pushed back
+    Kinematic {
+        vel: Vector2,
+        angular_vel: f32,
     },
     Static,
 }
 
+/// 1 / `value` for masses and inertias. 0 if it is not positive, e.g. for shapes without an area,
+/// so that impulses can't move the object instead of giving it an infinite velocity
+pub(crate) fn get_inverse(value: f32) -> f32 {
+    if value > 0. && value.is_finite() {
+        1. / value
+    } else {
+        0.
+    }
+}
+
 impl PhysicsAddition {
+    /// a mass or inertia that is not positive can't be changed by impulses, see `get_inverse`
+    pub fn new_dynamic(mass: f32, inertia: f32, accel: Vector2) -> PhysicsAddition {
+        PhysicsAddition::Dynamic {
+            accel,
+            gravity_scale: 1.,
+            vel: Vector2::zero(),
+            mass,
+            inv_mass: get_inverse(mass),
+            force: Vector2::zero(),
+            linear_damping: 0.,
+            angular_vel: 0.,
+            inertia,
+            inv_inertia: get_inverse(inertia),
+            torque: 0.,
+            angular_damping: 0.,
+            sleeping: false,
+            sleep_time: 0.,
+        }
+    }
+
+    pub fn new_kinematic(vel: Vector2, angular_vel: f32) -> PhysicsAddition {
+        PhysicsAddition::Kinematic { vel, angular_vel }
+    }
+
     pub fn get_masses(&self) -> (f32, f32) {
         if let PhysicsAddition::Dynamic { mass, inv_mass, .. } = self {
             (*mass, *inv_mass)
@@ -26,17 +90,184 @@ impl PhysicsAddition {
         }
     }
 
+    pub fn get_inertias(&self) -> (f32, f32) {
+        if let PhysicsAddition::Dynamic {
+            inertia,
+            inv_inertia,
+            ..
+        } = self
+        {
+            (*inertia, *inv_inertia)
+        } else {
+            (f32::INFINITY, 0.)
+        }
+    }
+
+    pub fn get_vel(&self) -> Vector2 {
+        match self {
+            PhysicsAddition::Dynamic { vel, .. } | PhysicsAddition::Kinematic { vel, .. } => *vel,
+            PhysicsAddition::Static => Vector2::zero(),
+        }
+    }
+
+    /// wakes the object up, setting the velocity should move it
     pub fn get_vel_mut(&mut self) -> Option<&mut Vector2> {
-        if let PhysicsAddition::Dynamic { ref mut vel, .. } = self {
-            Some(vel)
+        self.wake_up();
+        match self {
+            PhysicsAddition::Dynamic { ref mut vel, .. }
+            | PhysicsAddition::Kinematic { ref mut vel, .. } => Some(vel),
+            PhysicsAddition::Static => None,
+        }
+    }
+
+    pub fn get_angular_vel(&self) -> f32 {
+        match self {
+            PhysicsAddition::Dynamic { angular_vel, .. }
+            | PhysicsAddition::Kinematic { angular_vel, .. } => *angular_vel,
+            PhysicsAddition::Static => 0.,
+        }
+    }
+
+    /// wakes the object up
+    pub fn get_angular_vel_mut(&mut self) -> Option<&mut f32> {
+        self.wake_up();
+        match self {
+            PhysicsAddition::Dynamic {
+                ref mut angular_vel,
+                ..
+            }
+            | PhysicsAddition::Kinematic {
+                ref mut angular_vel,
+                ..
+            } => Some(angular_vel),
+            PhysicsAddition::Static => None,
+        }
+    }
+
+    /// velocity of the point at `r` (relative to the object position)
+    pub fn get_point_vel(&self, r: Vector2) -> Vector2 {
+        self.get_vel() + cross_scalar_vec(self.get_angular_vel(), r)
+    }
+
+    /// applies an impulse at `r` (relative to the object position), changes linear and angular velocity
+    pub fn add_impulse(&mut self, impulse: Vector2, r: Vector2) {
+        if let PhysicsAddition::Dynamic {
+            ref mut vel,
+            ref mut angular_vel,
+            inv_mass,
+            inv_inertia,
+            ..
+        } = self
+        {
+            *vel += impulse * *inv_mass;
+            *angular_vel += cross(r, impulse) * *inv_inertia;
+        }
+    }
+
+    /// changes only the angular velocity, like an impulse at a distance from the object position
+    pub fn add_angular_impulse(&mut self, impulse: f32) {
+        if let PhysicsAddition::Dynamic {
+            ref mut angular_vel,
+            inv_inertia,
+            ..
+        } = self
+        {
+            *angular_vel += impulse * *inv_inertia;
+        }
+    }
+
+    /// adds a force at `r` (relative to the object position) until the end of the next physics step
+    pub fn add_force(&mut self, added_force: Vector2, r: Vector2) {
+        self.wake_up();
+        if let PhysicsAddition::Dynamic {
+            ref mut force,
+            ref mut torque,
+            ..
+        } = self
+        {
+            *force += added_force;
+            *torque += cross(r, added_force);
+        }
+    }
+
+    pub fn add_torque(&mut self, added_torque: f32) {
+        self.wake_up();
+        if let PhysicsAddition::Dynamic { ref mut torque, .. } = self {
+            *torque += added_torque;
+        }
+    }
+
+    pub fn is_sleeping(&self) -> bool {
+        matches!(self, PhysicsAddition::Dynamic { sleeping: true, .. })
+    }
+
+    pub fn get_sleep_time(&self) -> f32 {
+        if let PhysicsAddition::Dynamic { sleep_time, .. } = self {
+            *sleep_time
         } else {
-            None
+            0.
+        }
+    }
+
+    /// counts how long the object has been slow, starts again when it is fast
+    pub fn update_sleep_time(&mut self, is_slow: bool, delta_time: f32) {
+        if let PhysicsAddition::Dynamic {
+            ref mut sleep_time, ..
+        } = self
+        {
+            *sleep_time = if is_slow {
+                *sleep_time + delta_time
+            } else {
+                0.
+            };
+        }
+    }
+
+    pub fn wake_up(&mut self) {
+        if let PhysicsAddition::Dynamic {
+            ref mut sleeping,
+            ref mut sleep_time,
+            ..
+        } = self
+        {
+            *sleeping = false;
+            *sleep_time = 0.;
+        }
+    }
+
+    pub fn fall_asleep(&mut self) {
+        if let PhysicsAddition::Dynamic {
+            ref mut sleeping,
+            ref mut vel,
+            ref mut angular_vel,
+            ..
+        } = self
+        {
+            *sleeping = true;
+            *vel = Vector2::zero();
+            *angular_vel = 0.;
+        }
+    }
+
+    /// called after the physics steps of every frame
+    pub fn clear_forces(&mut self) {
+        if let PhysicsAddition::Dynamic {
+            ref mut force,
+            ref mut torque,
+            ..
+        } = self
+        {
+            *force = Vector2::zero();
+            *torque = 0.;
         }
     }
 
     pub fn is_dynamic(&self) -> bool {
         matches!(self, PhysicsAddition::Dynamic { .. })
     }
+    pub fn is_kinematic(&self) -> bool {
+        matches!(self, PhysicsAddition::Kinematic { .. })
+    }
     pub fn is_static(&self) -> bool {
         matches!(self, PhysicsAddition::Static)
     }
@@ -50,25 +281,36 @@ impl Polygon {
             let vector_relative: Vector2 = Vector2::new(0., 1.).scale_by(radius).rotated(angle);
             corners.push(pos + vector_relative);
         }
-        let mut result: Polygon = Polygon {
-            corners,
-            bounding_box: Rectangle::default(),
-        };
-        result.update_bounding_box();
-        result
+        Polygon::from_valid_corners(pos, corners)
     }
 
     pub fn new_polygon_line(start: Vector2, end: Vector2, thickness: f32) -> Polygon {
         let u_normal: Vector2 = (end - start).normalized();
         let u_tangent: Vector2 = Vector2::new(-u_normal.y, u_normal.x);
 
-        let mut corners: Vec<Vector2> = Vec::new();
-        corners.push(end - u_tangent * thickness);
-        corners.push(start - u_tangent * thickness);
-        corners.push(start + u_tangent * thickness);
-        corners.push(end + u_tangent * thickness);
+        let corners: Vec<Vector2> = vec![
+            end + u_tangent * thickness,
+            start + u_tangent * thickness,
+            start - u_tangent * thickness,
+            end - u_tangent * thickness,
+        ];
+
+        Polygon::from_valid_corners((start + end) / 2., corners)
+    }
+
+    /// `corners` are in world space, `pos` is the point the polygon rotates around.
+    /// They have to form a convex outline ordered like the ones of `new_regular_polygon`,
+    /// concave outlines go through `Shape::new_concave`
+    pub fn from_corners(pos: Vector2, corners: Vec<Vector2>) -> Result<Polygon, PolygonError> {
+        decomposition::validate_convex_outline(&corners)?;
+        Ok(Polygon::from_valid_corners(pos, corners))
+    }
 
+    /// `from_corners` without the checks, for outlines that are convex and wound outwards by construction
+    pub(crate) fn from_valid_corners(pos: Vector2, corners: Vec<Vector2>) -> Polygon {
+        let local_corners: Vec<Vector2> = corners.iter().map(|&c| c - pos).collect();
         let mut result: Polygon = Polygon {
+            local_corners,
             corners,
             bounding_box: Rectangle::default(),
         };
@@ -76,6 +318,71 @@ impl Polygon {
         result
     }
 
+    /// positive if the corners are ordered like the ones of `new_regular_polygon`
+    pub fn get_signed_area_of(corners: &[Vector2]) -> f32 {
+        let mut sum: f32 = 0.;
+        for i in 0..corners.len() {
+            sum += cross(corners[i], corners[(i + 1) % corners.len()]);
+        }
+        sum / 2.
+    }
+
+    pub fn get_area(&self) -> f32 {
+        Self::get_signed_area_of(&self.corners).abs()
+    }
+
+    pub fn get_edge(&self, i: usize) -> (Vector2, Vector2) {
+        (self.corners[i], self.corners[(i + 1) % self.corners.len()])
+    }
+
+    pub fn get_outward_normal(&self, i: usize) -> Vector2 {
+        let (c1, c2) = self.get_edge(i);
+        let edge: Vector2 = c2 - c1;
+        Vector2::new(edge.y, -edge.x).normalized()
+    }
+
+    /// index of the edge whose outward normal is the most aligned with `u_dir`
+    pub fn get_best_edge(&self, u_dir: Vector2) -> usize {
+        let mut best_index: usize = 0;
+        let mut best_value: f32 = f32::NEG_INFINITY;
+        for i in 0..self.corners.len() {
+            let value: f32 = self.get_outward_normal(i).dot(u_dir);
+            if value > best_value {
+                best_value = value;
+                best_index = i;
+            }
+        }
+        best_index
+    }
+
+    /// moment of inertia around the object position for a polygon of uniform density,
+    /// the position doesn't have to be inside the polygon
+    pub fn get_moment_of_inertia(&self, mass: f32) -> f32 {
+        let corners: &Vec<Vector2> = &self.local_corners;
+        let mut numerator: f32 = 0.;
+        let mut denominator: f32 = 0.;
+        for i in 0..corners.len() {
+            let a: Vector2 = corners[i];
+            let b: Vector2 = corners[(i + 1) % corners.len()];
+            // signed, the triangles of edges facing away from the position are subtracted
+            let c: f32 = cross(a, b);
+            numerator += c * (a.dot(a) + a.dot(b) + b.dot(b));
+            denominator += c;
+        }
+        if denominator == 0. {
+            return 0.;
+        }
+        mass / 6. * numerator / denominator
+    }
+
+    /// recomputes the world space corners from the local corners
+    pub fn update_corners(&mut self, pos: Vector2, rotation: f32) {
+        for (corner, local) in self.corners.iter_mut().zip(&self.local_corners) {
+            *corner = pos + local.rotated(rotation);
+        }
+        self.update_bounding_box();
+    }
+
     pub fn move_relative(&mut self, added_pos: &Vector2) {
         for corner in &mut self.corners {
             *corner += *added_pos;
@@ -102,12 +409,29 @@ impl Polygon {
         };
     }
 
-    pub fn render(&self, d: &mut RaylibDrawHandle, color: Color) {
+    /// draws the polygon at the given transform, which can differ from the simulated one
+    pub fn render(
+        &self,
+        commands: &mut Vec<DrawCommand>,
+        pos: Vector2,
+        rotation: f32,
+        color: Color,
+    ) {
         // polygon
-        for i in 0..self.corners.len() {
-            let first_corner: &Vector2 = &self.corners[i];
-            let second_corner: &Vector2 = &self.corners[(i + 1) % self.corners.len()];
-            d.draw_line_ex(first_corner, second_corner, 5., color);
+        let corners: Vec<Vector2> = self
+            .local_corners
+            .iter()
+            .map(|c| pos + c.rotated(rotation))
+            .collect();
+        for i in 0..corners.len() {
+            let first_corner: &Vector2 = &corners[i];
+            let second_corner: &Vector2 = &corners[(i + 1) % corners.len()];
+            commands.push(DrawCommand::Line {
+                start: *first_corner,
+                end: *second_corner,
+                thickness: 5.,
+                color,
+            });
         }
 
         // bounding box
@@ -120,3 +444,63 @@ impl Polygon {
          */
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inertia_of_polygon_away_from_its_position() {
+        let corners: Vec<Vector2> = vec![
+            Vector2::new(100., -20.),
+            Vector2::new(140., -20.),
+            Vector2::new(140., 20.),
+            Vector2::new(100., 20.),
+        ];
+        let polygon: Polygon = Polygon::from_corners(Vector2::zero(), corners).unwrap();
+        // parallel axis theorem: square around its center plus the distance to the position
+        let expected: f32 = (40. * 40. + 40. * 40.) / 12. + 120. * 120.;
+        assert!((polygon.get_moment_of_inertia(1.) - expected).abs() < 0.01 * expected);
+    }
+
+    #[test]
+    fn massless_dynamic_object_is_not_moved_by_impulses() {
+        let mut physics: PhysicsAddition = PhysicsAddition::new_dynamic(0., -1., Vector2::zero());
+        assert_eq!(physics.get_masses().1, 0.);
+        assert_eq!(physics.get_inertias().1, 0.);
+        physics.add_impulse(Vector2::new(10., 0.), Vector2::new(0., 5.));
+        assert_eq!(physics.get_vel(), Vector2::zero());
+        assert_eq!(physics.get_angular_vel(), 0.);
+    }
+
+    #[test]
+    fn from_corners_rejects_what_it_can_not_collide() {
+        let v = Vector2::new;
+        let arrow: Vec<Vector2> = vec![v(0., 0.), v(50., 20.), v(100., 0.), v(50., 100.)];
+        assert_eq!(
+            Polygon::from_corners(v(50., 50.), arrow).err(),
+            Some(PolygonError::NotConvex)
+        );
+        let reversed: Vec<Vector2> = vec![v(0., 0.), v(0., 10.), v(10., 10.), v(10., 0.)];
+        assert_eq!(
+            Polygon::from_corners(v(5., 5.), reversed).err(),
+            Some(PolygonError::WrongWinding)
+        );
+    }
+
+    #[test]
+    fn built_in_polygons_are_valid_outlines() {
+        let line: Polygon = Polygon::new_polygon_line(Vector2::zero(), Vector2::new(100., 30.), 4.);
+        assert_eq!(
+            decomposition::validate_convex_outline(&line.corners),
+            Ok(())
+        );
+        for corner_count in 3..=8 {
+            let polygon: Polygon = Polygon::new_regular_polygon(Vector2::zero(), corner_count, 20.);
+            assert_eq!(
+                decomposition::validate_convex_outline(&polygon.corners),
+                Ok(())
+            );
+        }
+    }
+}
@@ -1,55 +1,176 @@
 use crate::rust_game_engine::engine_core::Scene;
+use crate::rust_game_engine::math::Vector2;
+use crate::rust_game_engine::object_store::ObjectId;
+use crate::rust_game_engine::physics::contact::Contact;
+use crate::rust_game_engine::physics::sleep;
 use std::collections::{HashMap, HashSet};
 
 impl Scene {
-    pub fn get_possible_collisions(&self) -> HashSet<(usize, usize)> {
+    pub fn get_possible_collisions(&self) -> HashSet<(ObjectId, ObjectId)> {
         // collision detection - broad phase
-        let obj_count = self.game_objects.len();
-
-        let mut cell_index_map: HashMap<(usize, usize), HashSet<usize>> = HashMap::new();
+        let mut cell_index_map: HashMap<(usize, usize), HashSet<ObjectId>> = HashMap::new();
         // fill map
-        for i in 0..obj_count {
+        for (id, obj) in self.game_objects.iter() {
             let cells_put_into: HashSet<(usize, usize)> =
-                self.game_objects[i].get_cell_positions(self.space_partitioning_grid_size);
+                obj.get_cell_positions(self.space_partitioning_grid_size);
 
             for cell in cells_put_into {
-                cell_index_map
-                    .entry(cell)
-                    .or_insert(HashSet::new())
-                    .insert(i);
+                cell_index_map.entry(cell).or_default().insert(id);
             }
         }
 
-        let mut possible_collision_pairs: HashSet<(usize, usize)> = HashSet::new();
+        let mut possible_collision_pairs: HashSet<(ObjectId, ObjectId)> = HashSet::new();
 
         for (_, objs_set) in cell_index_map {
-            let objs_vec: Vec<usize> = Vec::from_iter(objs_set);
+            let objs_vec: Vec<ObjectId> = Vec::from_iter(objs_set);
 
-            for &real_idx_1 in &objs_vec {
-                for &real_idx_2 in &objs_vec {
-                    if real_idx_1 < real_idx_2 {
-                        possible_collision_pairs.insert((real_idx_1, real_idx_2));
+            for &id_1 in &objs_vec {
+                for &id_2 in &objs_vec {
+                    if id_1 < id_2 {
+                        possible_collision_pairs.insert((id_1, id_2));
                     }
                 }
             }
         }
+        // collision filters and joints, before the narrow phase
+        let joined_pairs: HashSet<(ObjectId, ObjectId)> = self
+            .joints
+            .values()
+            .filter(|joint| !joint.collide_connected)
+            .filter_map(|joint| {
+                let (i, j) = (joint.a, joint.get_object_b()?);
+                Some((i.min(j), i.max(j)))
+            })
+            .collect();
+        possible_collision_pairs.retain(|&(i, j)| {
+            if joined_pairs.contains(&(i, j)) {
+                return false;
+            }
+            match (self.game_objects.get(i), self.game_objects.get(j)) {
+                (Some(a), Some(b)) => a.collision_filter.should_collide(&b.collision_filter),
+                _ => false,
+            }
+        });
         possible_collision_pairs
     }
 
     pub fn filter_real_collisions(
         &self,
-        mut possible_collisions: HashSet<(usize, usize)>,
-    ) -> Vec<(usize, usize)> {
+        mut possible_collisions: HashSet<(ObjectId, ObjectId)>,
+    ) -> Vec<Contact> {
         // collision detection - narrow phase
-        possible_collisions.drain().filter(|&(i, j)| {
-            self.game_objects[i].get_collision_axis_and_overlap(&self.game_objects[j]).is_some()
-        }).collect()
+        // sorted, so that the result does not depend on the hash set order
+        let mut pairs: Vec<(ObjectId, ObjectId)> = possible_collisions.drain().collect();
+        pairs.sort();
+
+        let mut contacts: Vec<Contact> = Vec::new();
+        for (i, j) in pairs {
+            let (Some(a), Some(b)) = (self.game_objects.get(i), self.game_objects.get(j)) else {
+                continue;
+            };
+            // only dynamic objects can be pushed
+            let any_dynamic: bool = a.physics.is_dynamic() || b.physics.is_dynamic();
+            if !any_dynamic || a.is_sensor || b.is_sensor {
+                continue;
+            }
+            // sleeping objects against sleeping or static ones, the contact tracker keeps their events
+            if !sleep::is_moving(a) && !sleep::is_moving(b) {
+                continue;
+            }
+            // one contact per pair of touching colliders, or pieces of concave colliders
+            for (collider_i, collider_j, normal, points) in a.get_contact_manifolds(b) {
+                contacts.push(Contact {
+                    collider_i,
+                    collider_j,
+                    ..Contact::new(i, j, a, b, &self.material_mixing, normal, points)
+                });
+            }
+        }
+        contacts
+    }
+
+    /// all objects that contain the point (world space), in the order they are drawn
+    pub fn get_objects_at(&self, point: Vector2) -> Vec<ObjectId> {
+        self.game_objects
+            .iter()
+            .filter(|(_, obj)| obj.contains_point(point))
+            .map(|(id, _)| id)
+            .collect()
+    }
+
+    /// the object drawn on top at the point (world space)
+    pub fn get_object_at(&self, point: Vector2) -> Option<ObjectId> {
+        self.get_objects_at(point).pop()
     }
 
-    pub fn resolve_collisions(&mut self, collisions: &Vec<(usize, usize)>) {
-        for &(i, j) in collisions {
-            let (left, right) = self.game_objects.split_at_mut(j);
-            left[i].resolve_collision_other(&mut right[0]);
+    /// (sensor, other) pairs that overlap, two sensors never overlap each other
+    pub fn filter_sensor_overlaps(
+        &self,
+        possible_collisions: &HashSet<(ObjectId, ObjectId)>,
+    ) -> Vec<(ObjectId, ObjectId)> {
+        let mut overlaps: Vec<(ObjectId, ObjectId)> = possible_collisions
+            .iter()
+            .filter_map(|&(i, j)| {
+                let (a, b) = (self.game_objects.get(i)?, self.game_objects.get(j)?);
+                if a.is_sensor == b.is_sensor || a.physics.is_static() && b.physics.is_static() {
+                    return None;
+                }
+                a.get_collision_axis_and_overlap(b)?;
+                Some(if a.is_sensor { (i, j) } else { (j, i) })
+            })
+            .collect();
+        overlaps.sort();
+        overlaps
+    }
+
+    /// solves the contacts together with all awake joints
+    pub fn resolve_collisions(&mut self, contacts: &mut [Contact], delta_time: f32) {
+        let joint_ids: Vec<ObjectId> = self
+            .joints
+            .iter()
+            .filter(|(_, joint)| self.is_joint_awake(joint))
+            .map(|(id, _)| id)
+            .collect();
+
+        for contact in contacts.iter_mut() {
+            let Some((a, b)) = self.game_objects.get_pair_mut(contact.i, contact.j) else {
+                continue;
+            };
+            contact.pre_step(a, b);
+        }
+        for &id in &joint_ids {
+            let Some(joint) = self.joints.get_mut(id) else {
+                continue;
+            };
+            let Some((a, b)) = joint.get_objects_mut(&mut self.game_objects) else {
+                continue;
+            };
+            joint.pre_step(a, b, delta_time);
+        }
+        for _ in 0..self.solver_config.velocity_iterations {
+            for &id in &joint_ids {
+                let Some(joint) = self.joints.get_mut(id) else {
+                    continue;
+                };
+                let Some((a, b)) = joint.get_objects_mut(&mut self.game_objects) else {
+                    continue;
+                };
+                joint.solve_velocity(a, b);
+            }
+            for contact in contacts.iter_mut() {
+                let Some((a, b)) = self.game_objects.get_pair_mut(contact.i, contact.j) else {
+                    continue;
+                };
+                contact.solve_velocity(a, b);
+            }
+        }
+        for _ in 0..self.solver_config.position_iterations {
+            for contact in contacts.iter() {
+                let Some((a, b)) = self.game_objects.get_pair_mut(contact.i, contact.j) else {
+                    continue;
+                };
+                contact.correct_positions(a, b);
+            }
         }
     }
 }
@@ -0,0 +1,109 @@
+use crate::rust_game_engine::engine_core::Scene;
+use crate::rust_game_engine::object_store::ObjectId;
+use std::collections::BTreeSet;
+
+/// Called once per event after the physics steps of a frame
+pub type SensorCallback = Box<dyn FnMut(&mut Scene, &SensorEvent)>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SensorEventKind {
+    Enter,
+    Exit,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SensorEvent {
+    pub kind: SensorEventKind,
+    pub sensor: ObjectId,
+    /// the object entering or leaving the sensor
+    pub other: ObjectId,
+}
+
+/// Remembers which objects overlapped which sensor in the last step
+#[derive(Default)]
+pub struct SensorTracker {
+    overlapping: BTreeSet<(ObjectId, ObjectId)>,
+    current: BTreeSet<(ObjectId, ObjectId)>,
+}
+
+impl SensorTracker {
+    pub fn new() -> SensorTracker {
+        SensorTracker::default()
+    }
+
+    /// (sensor, other) pairs overlapping in one sub step
+    pub fn add_overlaps(&mut self, overlaps: &[(ObjectId, ObjectId)]) {
+        self.current.extend(overlaps.iter().copied());
+    }
+
+    /// enter and exit events compared to the last step
+    pub fn finish_step(&mut self) -> Vec<SensorEvent> {
+        let entered = self
+            .current
+            .difference(&self.overlapping)
+            .map(|&(sensor, other)| SensorEvent {
+                kind: SensorEventKind::Enter,
+                sensor,
+                other,
+            });
+        let exited = self
+            .overlapping
+            .difference(&self.current)
+            .map(|&(sensor, other)| SensorEvent {
+                kind: SensorEventKind::Exit,
+                sensor,
+                other,
+            });
+        let events: Vec<SensorEvent> = entered.chain(exited).collect();
+
+        self.overlapping = std::mem::take(&mut self.current);
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rust_game_engine::math::Vector2;
+    use crate::rust_game_engine::physics::game_object::PhysicsObject;
+    use crate::rust_game_engine::physics::material::Material;
+
+    #[test]
+    fn body_moving_through_a_sensor_enters_and_exits_without_collision() {
+        let mut scene: Scene = Scene::new_headless(1. / 60.);
+        scene.disable_gravity();
+        let sensor: ObjectId = scene.add_game_object(
+            PhysicsObject::generate_ground(Vector2::new(500., 300.)).into_sensor(),
+        );
+        let ball: PhysicsObject = PhysicsObject::new_circle(
+            Vector2::new(300., 300.),
+            10.,
+            Material::default(),
+            "ball".to_string(),
+        );
+        let ball: ObjectId = scene.add_game_object(ball);
+        let vel: Vector2 = Vector2::new(300., 0.);
+        if let Some(ball_vel) = scene
+            .get_game_object_mut(ball)
+            .and_then(|obj| obj.physics.get_vel_mut())
+        {
+            *ball_vel = vel;
+        }
+
+        let mut events: Vec<SensorEvent> = vec![];
+        for _ in 0..90 {
+            scene.frame_logic(1. / 60.);
+            events.extend(scene.sensor_events.iter().copied());
+        }
+
+        let kinds: Vec<SensorEventKind> = events.iter().map(|event| event.kind).collect();
+        assert_eq!(kinds, vec![SensorEventKind::Enter, SensorEventKind::Exit]);
+        assert!(events
+            .iter()
+            .all(|event| event.sensor == sensor && event.other == ball));
+        // passed straight through
+        let ball: &PhysicsObject = scene.get_game_object(ball).unwrap();
+        assert_eq!(ball.physics.get_vel(), vel);
+        assert!(ball.obj.pos.x > 700.);
+    }
+}
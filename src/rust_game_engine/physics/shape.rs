@@ -0,0 +1,380 @@
+use crate::rust_game_engine::backend::DrawCommand;
+use crate::rust_game_engine::color::Color;
+use crate::rust_game_engine::math::{Rectangle, Vector2};
+use crate::rust_game_engine::physics::decomposition::{self, PolygonError};
+use crate::rust_game_engine::physics::physics_addition::Polygon;
+use crate::rust_game_engine::physics::vector_math::closest_point_on_segment;
+use std::f32::consts::{PI, TAU};
+
+/// segments used to draw round outlines
+const ROUND_SEGMENTS: usize = 24;
+
+/// Collider geometry of an object
+pub enum Shape {
+    Polygon(Polygon),
+    Circle(Circle),
+    Capsule(Capsule),
+    /// convex pieces of a concave outline, tested one by one
+    Compound(Vec<Shape>),
+}
+
+pub struct Circle {
+    pub radius: f32,
+    /// center in world space
+    pub center: Vector2,
+    pub bounding_box: Rectangle,
+}
+
+/// Rectangle with round ends, the axis is along x before rotation
+pub struct Capsule {
+    /// distance from the object position to the center of each round end
+    pub half_length: f32,
+    pub radius: f32,
+    /// centers of the round ends in world space
+    pub start: Vector2,
+    pub end: Vector2,
+    pub bounding_box: Rectangle,
+}
+
+impl Circle {
+    pub fn new(pos: Vector2, radius: f32) -> Circle {
+        let mut result: Circle = Circle {
+            radius,
+            center: pos,
+            bounding_box: Rectangle::default(),
+        };
+        result.update_bounding_box();
+        result
+    }
+
+    pub fn update_bounding_box(&mut self) {
+        self.bounding_box = Rectangle::new(
+            self.center.x - self.radius,
+            self.center.y - self.radius,
+            2. * self.radius,
+            2. * self.radius,
+        );
+    }
+}
+
+impl Capsule {
+    pub fn new(pos: Vector2, half_length: f32, radius: f32) -> Capsule {
+        let mut result: Capsule = Capsule {
+            half_length,
+            radius,
+            start: pos,
+            end: pos,
+            bounding_box: Rectangle::default(),
+        };
+        result.update_transform(pos, 0.);
+        result
+    }
+
+    pub fn update_transform(&mut self, pos: Vector2, rotation: f32) {
+        let half_axis: Vector2 = Vector2::new(self.half_length, 0.).rotated(rotation);
+        self.start = pos - half_axis;
+        self.end = pos + half_axis;
+        self.update_bounding_box();
+    }
+
+    pub fn update_bounding_box(&mut self) {
+        let min_x: f32 = self.start.x.min(self.end.x) - self.radius;
+        let min_y: f32 = self.start.y.min(self.end.y) - self.radius;
+        let max_x: f32 = self.start.x.max(self.end.x) + self.radius;
+        let max_y: f32 = self.start.y.max(self.end.y) + self.radius;
+        self.bounding_box = Rectangle::new(min_x, min_y, max_x - min_x, max_y - min_y);
+    }
+}
+
+impl Shape {
+    pub fn new_circle(pos: Vector2, radius: f32) -> Shape {
+        Shape::Circle(Circle::new(pos, radius))
+    }
+
+    pub fn new_capsule(pos: Vector2, half_length: f32, radius: f32) -> Shape {
+        Shape::Capsule(Capsule::new(pos, half_length, radius))
+    }
+
+    /// concave outlines are split into convex pieces. `corners` are in world space
+    /// and have to be ordered clockwise on screen, `pos` is the point the shape rotates around
+    pub fn new_concave(pos: Vector2, corners: &[Vector2]) -> Result<Shape, PolygonError> {
+        let mut pieces: Vec<Shape> = decomposition::decompose(corners)?
+            .into_iter()
+            .map(|piece| Shape::Polygon(Polygon::from_valid_corners(pos, piece)))
+            .collect();
+        if pieces.len() == 1 {
+            return Ok(pieces.remove(0));
+        }
+        Ok(Shape::Compound(pieces))
+    }
+
+    /// the convex pieces, just the shape itself if it isn't a compound
+    pub fn get_pieces(&self) -> &[Shape] {
+        match self {
+            Shape::Compound(pieces) => pieces,
+            _ => std::slice::from_ref(self),
+        }
+    }
+
+    pub fn get_bounding_box(&self) -> Rectangle {
+        match self {
+            Shape::Polygon(polygon) => polygon.bounding_box,
+            Shape::Circle(circle) => circle.bounding_box,
+            Shape::Capsule(capsule) => capsule.bounding_box,
+            Shape::Compound(pieces) => {
+                combine_bounding_boxes(pieces.iter().map(|piece| piece.get_bounding_box()))
+            }
+        }
+    }
+
+    pub fn get_area(&self) -> f32 {
+        match self {
+            Shape::Polygon(polygon) => polygon.get_area(),
+            Shape::Circle(circle) => PI * circle.radius * circle.radius,
+            Shape::Capsule(capsule) => {
+                let r: f32 = capsule.radius;
+                4. * r * capsule.half_length + PI * r * r
+            }
+            Shape::Compound(pieces) => pieces.iter().map(|piece| piece.get_area()).sum(),
+        }
+    }
+
+    /// center of the area relative to the shape origin, before rotation
+    pub fn get_centroid(&self) -> Vector2 {
+        match self {
+            Shape::Polygon(polygon) => decomposition::get_centroid(&polygon.local_corners),
+            Shape::Circle(_) | Shape::Capsule(_) => Vector2::zero(),
+            Shape::Compound(pieces) => {
+                let area: f32 = self.get_area();
+                pieces.iter().fold(Vector2::zero(), |sum, piece| {
+                    sum + piece.get_centroid() * (piece.get_area() / area)
+                })
+            }
+        }
+    }
+
+    /// moment of inertia around the shape origin for a shape of uniform density
+    pub fn get_moment_of_inertia(&self, mass: f32) -> f32 {
+        match self {
+            Shape::Polygon(polygon) => polygon.get_moment_of_inertia(mass),
+            Shape::Circle(circle) => mass * circle.radius * circle.radius / 2.,
+            Shape::Capsule(capsule) => {
+                let (r, h): (f32, f32) = (capsule.radius, capsule.half_length);
+                let density: f32 = mass / self.get_area();
+                let box_mass: f32 = density * 4. * r * h;
+                let circle_mass: f32 = density * PI * r * r;
+
+                // two half circles, their centroids are 4r/(3 pi) away from the round end centers
+                let centroid_offset: f32 = 4. * r / (3. * PI);
+                let circle_inertia: f32 =
+                    circle_mass * (0.5 * r * r + h * h + 2. * h * centroid_offset);
+                let box_inertia: f32 = box_mass * (4. * r * r + 4. * h * h) / 12.;
+                circle_inertia + box_inertia
+            }
+            Shape::Compound(pieces) => {
+                let area: f32 = self.get_area();
+                pieces
+                    .iter()
+                    .map(|piece| piece.get_moment_of_inertia(mass * piece.get_area() / area))
+                    .sum()
+            }
+        }
+    }
+
+    /// recomputes the world space geometry from the object transform
+    pub fn update_transform(&mut self, pos: Vector2, rotation: f32) {
+        match self {
+            Shape::Polygon(polygon) => polygon.update_corners(pos, rotation),
+            Shape::Circle(circle) => {
+                circle.center = pos;
+                circle.update_bounding_box();
+            }
+            Shape::Capsule(capsule) => capsule.update_transform(pos, rotation),
+            Shape::Compound(pieces) => {
+                for piece in pieces {
+                    piece.update_transform(pos, rotation);
+                }
+            }
+        }
+    }
+
+    pub fn move_relative(&mut self, added_pos: &Vector2) {
+        match self {
+            Shape::Polygon(polygon) => polygon.move_relative(added_pos),
+            Shape::Circle(circle) => {
+                circle.center += *added_pos;
+                circle.update_bounding_box();
+            }
+            Shape::Capsule(capsule) => {
+                capsule.start += *added_pos;
+                capsule.end += *added_pos;
+                capsule.update_bounding_box();
+            }
+            Shape::Compound(pieces) => {
+                for piece in pieces {
+                    piece.move_relative(added_pos);
+                }
+            }
+        }
+    }
+
+    /// the shape is every point within `get_radius` of the convex hull of these points.
+    /// Like the other collision helpers it is meant for convex shapes, compounds return
+    /// the values of all pieces and should be split with `get_pieces` instead
+    pub fn get_core_points(&self) -> Vec<Vector2> {
+        match self {
+            Shape::Polygon(polygon) => polygon.corners.clone(),
+            Shape::Circle(circle) => vec![circle.center],
+            Shape::Capsule(capsule) => vec![capsule.start, capsule.end],
+            Shape::Compound(pieces) => pieces
+                .iter()
+                .flat_map(|piece| piece.get_core_points())
+                .collect(),
+        }
+    }
+
+    pub fn get_radius(&self) -> f32 {
+        match self {
+            Shape::Polygon(_) | Shape::Compound(_) => 0.,
+            Shape::Circle(circle) => circle.radius,
+            Shape::Capsule(capsule) => capsule.radius,
+        }
+    }
+
+    /// unit normals of the flat sides
+    pub fn get_face_normals(&self) -> Vec<Vector2> {
+        match self {
+            Shape::Polygon(polygon) => (0..polygon.corners.len())
+                .map(|i| polygon.get_outward_normal(i))
+                .collect(),
+            Shape::Circle(_) => vec![],
+            Shape::Capsule(capsule) => {
+                let axis: Vector2 = (capsule.end - capsule.start).normalized();
+                vec![Vector2::new(-axis.y, axis.x), Vector2::new(axis.y, -axis.x)]
+            }
+            Shape::Compound(pieces) => pieces
+                .iter()
+                .flat_map(|piece| piece.get_face_normals())
+                .collect(),
+        }
+    }
+
+    /// core edge whose normal is the most aligned with `u_dir`, both points are the same for circles
+    pub fn get_best_edge(&self, u_dir: Vector2) -> (Vector2, Vector2) {
+        match self {
+            Shape::Polygon(polygon) => polygon.get_edge(polygon.get_best_edge(u_dir)),
+            Shape::Circle(circle) => (circle.center, circle.center),
+            Shape::Capsule(capsule) => (capsule.start, capsule.end),
+            Shape::Compound(pieces) => {
+                // edge of the piece that reaches the furthest in `u_dir`
+                let mut best: (Vector2, Vector2) = (Vector2::zero(), Vector2::zero());
+                let mut best_value: f32 = f32::NEG_INFINITY;
+                for piece in pieces {
+                    let edge: (Vector2, Vector2) = piece.get_best_edge(u_dir);
+                    let value: f32 = u_dir.dot(edge.0).max(u_dir.dot(edge.1));
+                    if value > best_value {
+                        best_value = value;
+                        best = edge;
+                    }
+                }
+                best
+            }
+        }
+    }
+
+    /// `true` if the point (world space) is inside the shape or on its outline
+    pub fn contains_point(&self, point: Vector2) -> bool {
+        match self {
+            Shape::Polygon(polygon) => (0..polygon.corners.len()).all(|i| {
+                polygon
+                    .get_outward_normal(i)
+                    .dot(point - polygon.corners[i])
+                    <= 0.
+            }),
+            Shape::Circle(circle) => {
+                (point - circle.center).length_sqr() <= circle.radius * circle.radius
+            }
+            Shape::Capsule(capsule) => {
+                let closest: Vector2 = closest_point_on_segment(point, capsule.start, capsule.end);
+                (point - closest).length_sqr() <= capsule.radius * capsule.radius
+            }
+            Shape::Compound(pieces) => pieces.iter().any(|piece| piece.contains_point(point)),
+        }
+    }
+
+    /// draws the shape at the given transform, which can differ from the simulated one
+    pub fn render(
+        &self,
+        commands: &mut Vec<DrawCommand>,
+        pos: Vector2,
+        rotation: f32,
+        color: Color,
+    ) {
+        let outline: Vec<Vector2> = match self {
+            Shape::Polygon(polygon) => {
+                polygon.render(commands, pos, rotation, color);
+                return;
+            }
+            Shape::Compound(pieces) => {
+                for piece in pieces {
+                    piece.render(commands, pos, rotation, color);
+                }
+                return;
+            }
+            Shape::Circle(circle) => {
+                // radius line, so that rolling is visible
+                commands.push(DrawCommand::Line {
+                    start: pos,
+                    end: pos + Vector2::new(circle.radius, 0.).rotated(rotation),
+                    thickness: 5.,
+                    color,
+                });
+                get_arc(pos, circle.radius, 0., TAU)
+            }
+            Shape::Capsule(capsule) => {
+                let half_axis: Vector2 = Vector2::new(capsule.half_length, 0.).rotated(rotation);
+                let mut outline: Vec<Vector2> =
+                    get_arc(pos + half_axis, capsule.radius, rotation - PI / 2., PI);
+                outline.extend(get_arc(
+                    pos - half_axis,
+                    capsule.radius,
+                    rotation + PI / 2.,
+                    PI,
+                ));
+                outline.push(outline[0]);
+                outline
+            }
+        };
+
+        for points in outline.windows(2) {
+            commands.push(DrawCommand::Line {
+                start: points[0],
+                end: points[1],
+                thickness: 5.,
+                color,
+            });
+        }
+    }
+}
+
+/// smallest rectangle containing all the boxes
+pub fn combine_bounding_boxes(boxes: impl IntoIterator<Item = Rectangle>) -> Rectangle {
+    let mut min: Vector2 = Vector2::new(f32::INFINITY, f32::INFINITY);
+    let mut max: Vector2 = Vector2::new(f32::NEG_INFINITY, f32::NEG_INFINITY);
+    for bb in boxes {
+        min = Vector2::new(min.x.min(bb.x), min.y.min(bb.y));
+        max = Vector2::new(max.x.max(bb.x + bb.width), max.y.max(bb.y + bb.height));
+    }
+    Rectangle::new(min.x, min.y, max.x - min.x, max.y - min.y)
+}
+
+/// points along an arc, counterclockwise on screen starting at `start_angle`
+fn get_arc(center: Vector2, radius: f32, start_angle: f32, angle: f32) -> Vec<Vector2> {
+    let segments: usize = ((ROUND_SEGMENTS as f32 * angle / TAU).ceil() as usize).max(1);
+    (0..=segments)
+        .map(|i| {
+            let a: f32 = start_angle + angle * i as f32 / segments as f32;
+            center + Vector2::new(radius, 0.).rotated(a)
+        })
+        .collect()
+}
@@ -0,0 +1,153 @@
+use crate::rust_game_engine::engine_core::Scene;
+use crate::rust_game_engine::math::Vector2;
+use crate::rust_game_engine::object_store::ObjectId;
+use crate::rust_game_engine::physics::contact::Contact;
+use crate::rust_game_engine::physics::game_object::PhysicsObject;
+use crate::rust_game_engine::physics::joint::Joint;
+use std::collections::HashMap;
+
+impl Scene {
+    /// wakes sleeping objects that are touched by an awake dynamic or a moving kinematic object
+    pub fn wake_touched_objects(&mut self, contacts: &[Contact]) {
+        for contact in contacts {
+            let Some((a, b)) = self.game_objects.get_pair_mut(contact.i, contact.j) else {
+                continue;
+            };
+            if a.physics.is_sleeping() && is_moving(b) {
+                a.physics.wake_up();
+            } else if b.physics.is_sleeping() && is_moving(a) {
+                b.physics.wake_up();
+            }
+        }
+    }
+
+    /// wakes sleeping objects that are joined to an awake dynamic or a moving kinematic object
+    pub fn wake_joined_objects(&mut self) {
+        for joint in self.joints.values() {
+            let Some(b) = joint.get_object_b() else {
+                continue;
+            };
+            let Some((a, b)) = self.game_objects.get_pair_mut(joint.a, b) else {
+                continue;
+            };
+            if a.physics.is_sleeping() && is_moving(b) {
+                a.physics.wake_up();
+            } else if b.physics.is_sleeping() && is_moving(a) {
+                b.physics.wake_up();
+            }
+        }
+    }
+
+    /// joints with an awake dynamic object, all others don't need to be solved
+    pub fn is_joint_awake(&self, joint: &Joint) -> bool {
+        joint.get_object_ids().into_iter().any(|id| {
+            self.game_objects
+                .get(id)
+                .is_some_and(|obj| obj.physics.is_dynamic() && !obj.physics.is_sleeping())
+        })
+    }
+
+    /// updates the sleep timers, then puts groups of touching objects to sleep
+    /// once all objects of the group have been slow for long enough
+    pub fn update_sleep(&mut self, delta_time: f32) {
+        if !self.sleep_config.enabled {
+            for obj in self.game_objects.values_mut() {
+                obj.physics.wake_up();
+            }
+            return;
+        }
+
+        let config = self.sleep_config;
+        for obj in self.game_objects.values_mut() {
+            if !obj.physics.is_dynamic() || obj.physics.is_sleeping() {
+                continue;
+            }
+            let is_slow: bool = obj.physics.get_vel().length() < config.linear_threshold
+                && obj.physics.get_angular_vel().abs() < config.angular_threshold;
+            obj.physics.update_sleep_time(is_slow, delta_time);
+        }
+
+        // groups of dynamic objects connected by contacts or joints
+        let mut parents: HashMap<ObjectId, ObjectId> = HashMap::new();
+        let joined_pairs: Vec<(ObjectId, ObjectId)> = self
+            .joints
+            .values()
+            .filter_map(|joint| Some((joint.a, joint.get_object_b()?)))
+            .collect();
+        for (i, j) in self
+            .contact_tracker
+            .get_touching_pairs()
+            .into_iter()
+            .chain(joined_pairs)
+        {
+            let both_dynamic: bool = [i, j].into_iter().all(|id| {
+                self.game_objects
+                    .get(id)
+                    .is_some_and(|obj| obj.physics.is_dynamic())
+            });
+            if both_dynamic {
+                let (root_i, root_j) = (find_root(&mut parents, i), find_root(&mut parents, j));
+                parents.insert(root_i, root_j);
+            }
+        }
+
+        let mut group_ready: HashMap<ObjectId, bool> = HashMap::new();
+        let ids: Vec<ObjectId> = self.game_objects.ids().collect();
+        for &id in &ids {
+            let Some(obj) = self.game_objects.get(id) else {
+                continue;
+            };
+            if !obj.physics.is_dynamic() {
+                continue;
+            }
+            let ready: bool =
+                obj.physics.is_sleeping() || obj.physics.get_sleep_time() >= config.time_to_sleep;
+            let root: ObjectId = find_root(&mut parents, id);
+            *group_ready.entry(root).or_insert(true) &= ready;
+        }
+
+        for id in ids {
+            let root: ObjectId = find_root(&mut parents, id);
+            if group_ready.get(&root) == Some(&true) {
+                if let Some(obj) = self.game_objects.get_mut(id) {
+                    obj.physics.fall_asleep();
+                }
+            }
+        }
+    }
+
+    pub fn get_sleeping_count(&self) -> usize {
+        self.game_objects
+            .values()
+            .filter(|obj| obj.physics.is_sleeping())
+            .count()
+    }
+}
+
+/// awake dynamic objects and kinematic objects with a velocity
+pub(crate) fn is_moving(obj: &PhysicsObject) -> bool {
+    if obj.physics.is_kinematic() {
+        obj.physics.get_vel() != Vector2::zero() || obj.physics.get_angular_vel() != 0.
+    } else {
+        obj.physics.is_dynamic() && !obj.physics.is_sleeping()
+    }
+}
+
+/// union find with path compression, objects without an entry are their own root
+fn find_root(parents: &mut HashMap<ObjectId, ObjectId>, id: ObjectId) -> ObjectId {
+    let mut root: ObjectId = id;
+    while let Some(&parent) = parents.get(&root) {
+        if parent == root {
+            break;
+        }
+        root = parent;
+    }
+
+    let mut current: ObjectId = id;
+    while current != root {
+        let next: ObjectId = parents[&current];
+        parents.insert(current, root);
+        current = next;
+    }
+    root
+}
@@ -0,0 +1,34 @@
+use crate::rust_game_engine::constants::*;
+
+/// When resting objects are taken out of the simulation
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SleepConfig {
+    pub enabled: bool,
+    /// px/s
+    pub linear_threshold: f32,
+    /// rad/s
+    pub angular_threshold: f32,
+    /// s an object has to stay below both thresholds before it falls asleep
+    pub time_to_sleep: f32,
+}
+
+impl SleepConfig {
+    pub fn new(linear_threshold: f32, angular_threshold: f32, time_to_sleep: f32) -> SleepConfig {
+        SleepConfig {
+            enabled: true,
+            linear_threshold,
+            angular_threshold,
+            time_to_sleep,
+        }
+    }
+}
+
+impl Default for SleepConfig {
+    fn default() -> Self {
+        SleepConfig::new(
+            SLEEP_LINEAR_THRESHOLD,
+            SLEEP_ANGULAR_THRESHOLD,
+            TIME_TO_SLEEP,
+        )
+    }
+}
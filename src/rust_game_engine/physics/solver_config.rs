@@ -0,0 +1,32 @@
+use crate::rust_game_engine::constants::*;
+
+/// Trades simulation speed for stability
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SolverConfig {
+    /// every physics step is split into this many smaller steps
+    pub sub_steps: usize,
+    /// passes over all contacts to resolve velocities
+    pub velocity_iterations: usize,
+    /// passes over all contacts to push overlapping objects apart
+    pub position_iterations: usize,
+}
+
+impl SolverConfig {
+    pub fn new(
+        sub_steps: usize,
+        velocity_iterations: usize,
+        position_iterations: usize,
+    ) -> SolverConfig {
+        SolverConfig {
+            sub_steps: sub_steps.max(1),
+            velocity_iterations,
+            position_iterations,
+        }
+    }
+}
+
+impl Default for SolverConfig {
+    fn default() -> Self {
+        SolverConfig::new(SUB_STEPS, VELOCITY_ITERATIONS, POSITION_ITERATIONS)
+    }
+}
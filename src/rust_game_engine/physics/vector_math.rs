@@ -0,0 +1,56 @@
+use crate::rust_game_engine::math::Vector2;
+
+/// z component of the 3d cross product of a and b
+pub fn cross(a: Vector2, b: Vector2) -> f32 {
+    a.x * b.y - a.y * b.x
+}
+
+/// cross product of a scalar (z axis) and a vector: s x v
+pub fn cross_scalar_vec(s: f32, v: Vector2) -> Vector2 {
+    Vector2::new(-s * v.y, s * v.x)
+}
+
+/// point on the segment a-b that is the closest to p
+pub fn closest_point_on_segment(p: Vector2, a: Vector2, b: Vector2) -> Vector2 {
+    let ab: Vector2 = b - a;
+    let length_sqr: f32 = ab.dot(ab);
+    if length_sqr == 0. {
+        return a;
+    }
+    let t: f32 = ((p - a).dot(ab) / length_sqr).clamp(0., 1.);
+    a + ab * t
+}
+
+/// closest points between the segments a1-a2 and b1-b2, the first one is on segment a
+pub fn closest_points_on_segments(
+    a1: Vector2,
+    a2: Vector2,
+    b1: Vector2,
+    b2: Vector2,
+) -> (Vector2, Vector2) {
+    let candidates: [(Vector2, Vector2); 4] = [
+        (a1, closest_point_on_segment(a1, b1, b2)),
+        (a2, closest_point_on_segment(a2, b1, b2)),
+        (closest_point_on_segment(b1, a1, a2), b1),
+        (closest_point_on_segment(b2, a1, a2), b2),
+    ];
+    let mut best: (Vector2, Vector2) = candidates[0];
+    for candidate in candidates {
+        if (candidate.1 - candidate.0).length_sqr() < (best.1 - best.0).length_sqr() {
+            best = candidate;
+        }
+    }
+
+    // crossing segments
+    let (da, db): (Vector2, Vector2) = (a2 - a1, b2 - b1);
+    let denominator: f32 = cross(da, db);
+    if denominator != 0. {
+        let t: f32 = cross(b1 - a1, db) / denominator;
+        let u: f32 = cross(b1 - a1, da) / denominator;
+        if (0. ..=1.).contains(&t) && (0. ..=1.).contains(&u) {
+            let p: Vector2 = a1 + da * t;
+            best = (p, p);
+        }
+    }
+    best
+}
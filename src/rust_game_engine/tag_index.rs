@@ -0,0 +1,70 @@
+use crate::rust_game_engine::object_store::ObjectId;
+use crate::rust_game_engine::physics::game_object::GameObject;
+use std::collections::{BTreeSet, HashMap};
+
+/// Object ids by name tag and by tag.
+/// Kept up to date by the scene, so names and tags of objects in a scene should be changed through it
+#[derive(Default)]
+pub struct TagIndex {
+    names: HashMap<String, BTreeSet<ObjectId>>,
+    tags: HashMap<String, BTreeSet<ObjectId>>,
+}
+
+impl TagIndex {
+    pub fn new() -> TagIndex {
+        TagIndex::default()
+    }
+
+    pub fn insert(&mut self, id: ObjectId, obj: &GameObject) {
+        self.names
+            .entry(obj.get_name_tag().to_string())
+            .or_default()
+            .insert(id);
+        for tag in obj.get_tags() {
+            self.tags.entry(tag.clone()).or_default().insert(id);
+        }
+    }
+
+    pub fn remove(&mut self, id: ObjectId, obj: &GameObject) {
+        Self::remove_from(&mut self.names, obj.get_name_tag(), id);
+        for tag in obj.get_tags() {
+            Self::remove_from(&mut self.tags, tag, id);
+        }
+    }
+
+    pub fn rename(&mut self, id: ObjectId, old_name: &str, new_name: &str) {
+        Self::remove_from(&mut self.names, old_name, id);
+        self.names
+            .entry(new_name.to_string())
+            .or_default()
+            .insert(id);
+    }
+
+    pub fn add_tag(&mut self, id: ObjectId, tag: &str) {
+        self.tags.entry(tag.to_string()).or_default().insert(id);
+    }
+
+    pub fn remove_tag(&mut self, id: ObjectId, tag: &str) {
+        Self::remove_from(&mut self.tags, tag, id);
+    }
+
+    /// all objects with this name tag, ordered by id
+    pub fn get_ids_by_name(&self, name: &str) -> impl Iterator<Item = ObjectId> + '_ {
+        self.names.get(name).into_iter().flatten().copied()
+    }
+
+    /// all objects with this tag, ordered by id
+    pub fn get_ids_with_tag(&self, tag: &str) -> impl Iterator<Item = ObjectId> + '_ {
+        self.tags.get(tag).into_iter().flatten().copied()
+    }
+
+    fn remove_from(map: &mut HashMap<String, BTreeSet<ObjectId>>, key: &str, id: ObjectId) {
+        if let Some(ids) = map.get_mut(key) {
+            ids.remove(&id);
+            // don't keep empty entries for the names of temporary objects
+            if ids.is_empty() {
+                map.remove(key);
+            }
+        }
+    }
+}